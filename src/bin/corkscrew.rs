@@ -1,6 +1,13 @@
 use clap::Parser;
-use corkscrew::{run, Cli, Settings};
-use std::error::Error;
+use corkscrew::{
+    bench, export, import, render, run, validate, Cli, Command, OutputFormat, Report, ReportFormat,
+    Settings, Severity,
+};
+use std::{
+    error::Error,
+    fs,
+    io::{self, IsTerminal},
+};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -9,11 +16,164 @@ async fn main() -> Result<(), Box<dyn Error>> {
         .try_into()
         .expect("Failed to parse provided arguments. Run 'corkscrew --help' for help.");
 
-    let results = run::go(settings).await?;
+    if let Some(Command::Bench(options)) = settings.command.clone() {
+        let reports = bench::go(settings, options).await?;
 
-    for (name, result) in results {
-        println!("{}\t{}\t{}", name, result.status(), result.url());
+        for report in reports {
+            println!(
+                "{}\t{}\t{:.2}%\t{:.2}/s\t{}ms\t{}ms\t{}ms\t{}ms",
+                report.name,
+                report.requests,
+                report.error_rate() * 100.0,
+                report.throughput(),
+                report.p50.as_millis(),
+                report.p90.as_millis(),
+                report.p99.as_millis(),
+                report.max.as_millis()
+            );
+        }
+
+        return Ok(());
+    }
+
+    if let Some(Command::Import(options)) = settings.command.clone() {
+        let into = options.into.clone();
+
+        let imported = import::go(options)?;
+
+        for warning in &imported.warnings {
+            eprintln!("warning: {warning}");
+        }
+
+        match (imported.yaml, into) {
+            (Some(yaml), _) => print!("{yaml}"),
+            (None, into) => eprintln!(
+                "Imported {} request{} into {}",
+                imported.count,
+                plural(imported.count),
+                into.unwrap_or_default().display()
+            ),
+        }
+
+        return Ok(());
+    }
+
+    if let Some(Command::Export(options)) = settings.command.clone() {
+        print!("{}", export::go(settings, options)?);
+
+        return Ok(());
+    }
+
+    if let Some(Command::Validate) = settings.command {
+        let problems = validate::go(&settings);
+
+        let errors = problems
+            .iter()
+            .filter(|problem| problem.severity == Severity::Error)
+            .count();
+
+        match settings.output {
+            OutputFormat::Table => {
+                for problem in &problems {
+                    eprintln!("{problem}");
+                }
+
+                let warnings = problems.len() - errors;
+
+                eprintln!(
+                    "{}: {} error{}, {} warning{}",
+                    settings.config_path.display(),
+                    errors,
+                    plural(errors),
+                    warnings,
+                    plural(warnings)
+                );
+            }
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&problems)?),
+            OutputFormat::Ndjson => {
+                for problem in &problems {
+                    println!("{}", serde_json::to_string(problem)?);
+                }
+            }
+        }
+
+        // Fail so validation can be used in CI
+        if errors > 0 {
+            std::process::exit(1);
+        }
+
+        return Ok(());
+    }
+
+    let color = !settings.no_color && io::stdout().is_terminal();
+    let stderr_color = !settings.no_color && io::stderr().is_terminal();
+
+    let output = settings.output;
+    let reports = settings.reports.clone();
+    let har = settings.har.clone();
+
+    // A test report written to stdout replaces the normal output
+    let quiet = reports.iter().any(|r| r.path.is_none());
+
+    let (verbose, show_secrets) = (settings.verbose, settings.show_secrets);
+    let extract = settings.extract.clone();
+    let stream = output == OutputFormat::Ndjson && !quiet && extract.is_none();
+
+    // Verbose output goes to stderr as each request completes, like curl
+    let report = run::go_with(settings, |outcome| {
+        if verbose > 0 {
+            eprint!(
+                "{}",
+                render::verbose::verbose(outcome, verbose, show_secrets, stderr_color)
+            );
+        }
+
+        if stream {
+            match render::json::ndjson(outcome) {
+                Ok(line) => println!("{line}"),
+                Err(e) => eprintln!("{}: {}", outcome.name, e),
+            }
+        }
+    })
+    .await?;
+
+    // Extracted values replace the table, or any output if given on the command line
+    let extracted = !quiet
+        && (extract.is_some() || output == OutputFormat::Table)
+        && render::extract::print(&report, extract.as_ref()).is_some();
+
+    if !quiet && !extracted {
+        match output {
+            OutputFormat::Table => render::table::print(&report, color)?,
+            OutputFormat::Json => println!("{}", render::json::json(&report)?),
+            OutputFormat::Ndjson => {}
+        }
+    }
+
+    for Report { format, path } in reports {
+        let contents = match format {
+            ReportFormat::Junit => render::junit::junit(&report),
+            ReportFormat::Tap => render::tap::tap(&report),
+        };
+
+        match path {
+            Some(path) => fs::write(&path, contents)
+                .unwrap_or_else(|e| panic!("Failed to write report to {}: {}", path.display(), e)),
+            None => print!("{contents}"),
+        }
+    }
+
+    if let Some(path) = har {
+        fs::write(&path, render::har::har(&report, show_secrets)?)
+            .unwrap_or_else(|e| panic!("Failed to write HAR to {}: {}", path.display(), e));
     }
 
     Ok(())
 }
+
+fn plural(n: usize) -> &'static str {
+    match n {
+        1 => "",
+        _ => "s",
+    }
+}
@@ -0,0 +1,435 @@
+use crate::types::{AuthType, Detail};
+
+/// Equivalent `curl` command for a request built from `detail`, on one line.
+pub fn curl(request: &reqwest::Request, detail: &Detail) -> String {
+    let mut url = request.url().clone();
+    let mut args = vec![String::from("curl")];
+
+    // Requests are always built with a body, which is `null` unless one is given
+    let has_body = detail.body.is_some() || detail.form.is_some();
+
+    // curl waits for a body in reply to `-X HEAD`
+    match request.method() {
+        &reqwest::Method::HEAD if !has_body => args.push(String::from("-I")),
+        method if method != reqwest::Method::GET || has_body => args.push(format!("-X {method}")),
+        _ => {}
+    }
+
+    // Basic auth is sent as a header, but is easier to read as credentials
+    let basic = match &detail.auth {
+        Some(AuthType::Basic { username, password }) => {
+            args.push(format!("-u {}", quote(&format!("{username}:{password}"))));
+            true
+        }
+        _ => false,
+    };
+
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url.set_fragment(None);
+
+    args.push(quote(url.as_str()));
+
+    let user_content_type = detail
+        .headers
+        .iter()
+        .flatten()
+        .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+
+    let mut headers: Vec<(&str, String)> = request
+        .headers()
+        .iter()
+        .filter(|(name, _)| has_body || user_content_type || *name != "content-type")
+        .filter(|(name, _)| !basic || *name != "authorization")
+        .map(|(name, value)| {
+            (
+                name.as_str(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            )
+        })
+        .collect();
+
+    headers.sort();
+
+    for (name, value) in headers {
+        args.push(format!("-H {}", quote(&format!("{name}: {value}"))));
+    }
+
+    if let Some(body) = request
+        .body()
+        .and_then(|b| b.as_bytes())
+        .filter(|_| has_body)
+    {
+        args.push(format!(
+            "--data-raw {}",
+            quote(&String::from_utf8_lossy(body))
+        ));
+    }
+
+    if let Some(timeout) = detail.timeout {
+        args.push(format!("--max-time {timeout}"));
+    }
+
+    args.join(" ")
+}
+
+/// Quotes a value for a POSIX shell.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Options which take a value that doesn't affect the request definition.
+const IGNORED_WITH_VALUE: [&str; 30] = [
+    "-o",
+    "--output",
+    "-x",
+    "--proxy",
+    "-U",
+    "--proxy-user",
+    "-w",
+    "--write-out",
+    "-c",
+    "--cookie-jar",
+    "-K",
+    "--config",
+    "-r",
+    "--range",
+    "-E",
+    "--cert",
+    "--key",
+    "--cacert",
+    "--capath",
+    "--connect-timeout",
+    "--retry",
+    "--retry-delay",
+    "--retry-max-time",
+    "--max-redirs",
+    "--limit-rate",
+    "--resolve",
+    "--connect-to",
+    "--interface",
+    "--dns-servers",
+    "--user-agent-file",
+];
+
+/// Request definitions for each `curl` command in `input`, such as those
+/// copied from browser developer tools. Anything other than `curl` commands
+/// is ignored.
+pub fn import(input: &str) -> Result<Vec<Detail>, String> {
+    commands(input)?
+        .into_iter()
+        .filter(|words| {
+            words.first().is_some_and(|command| {
+                let command = command.rsplit('/').next().unwrap_or_default();
+                command == "curl" || command == "curl.exe"
+            })
+        })
+        .map(|words| detail(&words[1..]))
+        .collect()
+}
+
+fn detail(args: &[String]) -> Result<Detail, String> {
+    let mut url = None;
+    let mut method = None;
+    let mut headers: Vec<(String, String)> = vec![];
+    let mut data: Vec<String> = vec![];
+    let mut json = false;
+    let mut get = false;
+    let mut user = None;
+    let mut timeout = None;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        // Short options may have their value attached, e.g. -XPOST
+        let (option, attached) = match arg.strip_prefix('-') {
+            Some(short) if !short.starts_with('-') && short.len() > 1 => match &short[..1] {
+                "X" | "H" | "d" | "u" | "A" | "e" | "b" | "m" | "F" | "T" => {
+                    (format!("-{}", &short[..1]), Some(short[1..].to_string()))
+                }
+                _ => (arg.clone(), None),
+            },
+            _ => (arg.clone(), None),
+        };
+
+        let mut value = || {
+            attached
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| format!("Missing value for curl option '{option}'."))
+        };
+
+        match option.as_str() {
+            "-X" | "--request" => method = Some(value()?.to_lowercase()),
+            "-H" | "--header" => {
+                let header = value()?;
+
+                if let Some((name, value)) = header.split_once(':') {
+                    headers.push((name.trim().to_string(), value.trim().to_string()));
+                }
+            }
+            "--data-raw" => data.push(value()?),
+            "-d" | "--data" | "--data-binary" | "--data-ascii" | "--json" => {
+                let value = value()?;
+
+                if value.starts_with('@') {
+                    return Err(format!(
+                        "Bodies read from files (curl {option} {value}) are not supported."
+                    ));
+                }
+
+                json |= option == "--json";
+                data.push(value);
+            }
+            "--data-urlencode" => {
+                let value = value()?;
+
+                // Without a name and value, `@` is the name of a file to read
+                if !value.contains('=') && value.contains('@') {
+                    return Err(format!(
+                        "Bodies read from files (curl {option} {value}) are not supported."
+                    ));
+                }
+
+                let encoded = match value.split_once('=') {
+                    Some((name, value)) => format!("{}={}", name, encode(value)),
+                    None => encode(&value),
+                };
+
+                data.push(encoded);
+            }
+            "-u" | "--user" => user = Some(value()?),
+            "-A" | "--user-agent" => headers.push((String::from("User-Agent"), value()?)),
+            "-e" | "--referer" => headers.push((String::from("Referer"), value()?)),
+            "-b" | "--cookie" => {
+                let cookie = value()?;
+
+                // Otherwise it's the name of a file to read cookies from
+                if cookie.contains('=') {
+                    headers.push((String::from("Cookie"), cookie));
+                }
+            }
+            "-m" | "--max-time" => {
+                let seconds = value()?;
+
+                timeout = Some(
+                    seconds
+                        .parse::<f64>()
+                        .map_err(|_| format!("Invalid curl --max-time '{seconds}'."))?
+                        .ceil() as u64,
+                );
+            }
+            "-F" | "--form" => {
+                return Err(String::from(
+                    "Multipart form data (curl -F) is not supported.",
+                ))
+            }
+            "-T" | "--upload-file" => {
+                return Err(String::from("Uploading files (curl -T) is not supported."))
+            }
+            "--url" => url = Some(value()?),
+            "-G" | "--get" => get = true,
+            "-I" | "--head" => method = Some(String::from("head")),
+            option if IGNORED_WITH_VALUE.contains(&option) => {
+                value()?;
+            }
+            option if option.starts_with('-') => {}
+            _ => url = Some(arg.clone()),
+        }
+    }
+
+    let url = url.ok_or_else(|| String::from("Missing URL in curl command."))?;
+
+    let url = match url.contains("://") {
+        true => url,
+        false => format!("http://{url}"),
+    };
+
+    let mut url = reqwest::Url::parse(&url)
+        .map_err(|e| format!("Invalid URL '{url}' in curl command: {e}"))?;
+
+    let data = data.join("&");
+
+    if get && !data.is_empty() {
+        let query = match url.query() {
+            Some(query) => format!("{query}&{data}"),
+            None => data.clone(),
+        };
+
+        url.set_query(Some(&query));
+    }
+
+    let has_body = !get && !data.is_empty();
+
+    let method = method.unwrap_or_else(|| match has_body {
+        true => String::from("post"),
+        false => String::from("get"),
+    });
+
+    // Media type without any parameters, such as the charset
+    let content_type = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| {
+            let media_type = value.split(';').next().unwrap_or_default();
+            media_type.trim().to_lowercase()
+        });
+
+    let mut detail = super::detail(&url, &method);
+
+    detail.timeout = timeout;
+
+    if has_body {
+        match content_type.as_deref() {
+            Some(t) if t.contains("json") => detail.body = Some(parse_json(&data)?),
+            None if json => detail.body = Some(parse_json(&data)?),
+            None | Some("application/x-www-form-urlencoded") => {
+                let form: Vec<(String, String)> = serde_urlencoded::from_str(&data)
+                    .map_err(|e| format!("Invalid form data in curl command: {e}"))?;
+
+                detail.form = Some(form.into_iter().collect());
+            }
+            Some(t) => {
+                return Err(format!(
+                    "Bodies of type '{t}' are not supported, only JSON and form data."
+                ))
+            }
+        }
+
+        // Set when building the request from the body or form
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+    }
+
+    if let Some(user) = user {
+        let (username, password) = user.split_once(':').unwrap_or((&user, ""));
+
+        detail.auth = Some(AuthType::Basic {
+            username: username.to_string(),
+            password: password.to_string(),
+        });
+    }
+
+    super::split_auth(&mut detail, &mut headers);
+
+    headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-length"));
+
+    if !headers.is_empty() {
+        detail.headers = Some(headers.into_iter().collect());
+    }
+
+    Ok(detail)
+}
+
+fn parse_json(data: &str) -> Result<serde_json::Value, String> {
+    serde_json::from_str(data).map_err(|e| format!("Invalid JSON body in curl command: {e}"))
+}
+
+fn encode(value: &str) -> String {
+    serde_urlencoded::to_string([("", value)])
+        .unwrap_or_default()
+        .trim_start_matches('=')
+        .to_string()
+}
+
+/// Splits shell command lines into the words of each command, handling
+/// quoting, escapes, line continuations and comments.
+fn commands(input: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut commands = vec![];
+    let mut words: Vec<String> = vec![];
+    let mut word: Option<String> = None;
+    let mut chars = input.chars().peekable();
+
+    let unclosed = || String::from("Unclosed quote in curl command.");
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' | '\r' => words.extend(word.take()),
+            '\n' | ';' | '|' | '&' => {
+                words.extend(word.take());
+
+                if !words.is_empty() {
+                    commands.push(std::mem::take(&mut words));
+                }
+            }
+            '#' if word.is_none() => while chars.next_if(|c| *c != '\n').is_some() {},
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.next_if_eq(&'\n').is_some() => {}
+                Some(c) => word.get_or_insert_with(String::new).push(c),
+                None => {}
+            },
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+
+                loop {
+                    match chars.next().ok_or_else(unclosed)? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+
+                loop {
+                    match chars.next().ok_or_else(unclosed)? {
+                        '"' => break,
+                        '\\' => match chars.next().ok_or_else(unclosed)? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            '\n' => {}
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            // ANSI-C quoting, used by browsers for bodies with special characters
+            '$' if chars.next_if_eq(&'\'').is_some() => {
+                let word = word.get_or_insert_with(String::new);
+
+                loop {
+                    match chars.next().ok_or_else(unclosed)? {
+                        '\'' => break,
+                        '\\' => match chars.next().ok_or_else(unclosed)? {
+                            'n' => word.push('\n'),
+                            't' => word.push('\t'),
+                            'r' => word.push('\r'),
+                            'x' => {
+                                let hex: String =
+                                    std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_hexdigit()))
+                                        .take(2)
+                                        .collect();
+
+                                word.extend(u8::from_str_radix(&hex, 16).ok().map(|b| b as char));
+                            }
+                            'u' => {
+                                let hex: String =
+                                    std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_hexdigit()))
+                                        .take(4)
+                                        .collect();
+
+                                word.extend(
+                                    u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32),
+                                );
+                            }
+                            c => word.push(c),
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    words.extend(word.take());
+
+    if !words.is_empty() {
+        commands.push(words);
+    }
+
+    Ok(commands)
+}
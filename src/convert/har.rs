@@ -0,0 +1,246 @@
+use serde::Deserialize;
+
+use super::slug;
+use crate::types::{selection::glob, Detail, Import};
+
+/// Headers which are set by the browser for the connection, or when building
+/// the request, rather than being part of the request itself.
+const IGNORED_HEADERS: [&str; 5] = [
+    "host",
+    "content-length",
+    "connection",
+    "keep-alive",
+    "accept-encoding",
+];
+
+#[derive(Deserialize)]
+struct Archive {
+    log: Log,
+}
+
+#[derive(Deserialize)]
+struct Log {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    request: Request,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<Header>,
+    #[serde(rename = "postData")]
+    post_data: Option<PostData>,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    name: String,
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct PostData {
+    #[serde(rename = "mimeType", default)]
+    mime_type: String,
+    text: Option<String>,
+    #[serde(default)]
+    params: Vec<Param>,
+}
+
+#[derive(Deserialize)]
+struct Param {
+    name: String,
+    value: Option<String>,
+}
+
+/// Request definitions for the requests in an HTTP Archive (HAR), such as
+/// saved from browser developer tools, along with warnings for anything which
+/// couldn't be converted.
+///
+/// Requests are grouped by the scheme, host and port they were sent to, so
+/// they can be replayed against another host by changing the group. Requests
+/// not matching the `--host`, `--method` and `--url` filters of `import` are
+/// skipped, as are any identical to one already imported. CORS preflight
+/// requests are sent by the browser, so are skipped unless `--method options`
+/// is given.
+pub fn import(input: &str, import: &Import) -> Result<(Vec<Detail>, Vec<String>), String> {
+    let archive: Archive =
+        serde_json::from_str(input).map_err(|e| format!("Invalid HAR file: {e}"))?;
+
+    let mut groups: Vec<Detail> = vec![];
+    let mut warnings = vec![];
+
+    for entry in archive.log.entries {
+        let request = entry.request;
+
+        let Ok(url) = reqwest::Url::parse(&request.url) else {
+            warnings.push(format!(
+                "Skipped request with invalid URL '{}'.",
+                request.url
+            ));
+            continue;
+        };
+
+        // Data URLs, WebSockets and the like aren't HTTP requests
+        if !matches!(url.scheme(), "http" | "https") || !included(&url, &request.method, import) {
+            continue;
+        }
+
+        let preflight = request.method.eq_ignore_ascii_case("options")
+            && request.headers.iter().any(|header| {
+                header
+                    .name
+                    .eq_ignore_ascii_case("access-control-request-method")
+            });
+
+        if preflight && import.methods.is_empty() {
+            continue;
+        }
+
+        let mut detail = super::detail(&url, &request.method);
+
+        let mut headers: Vec<(String, String)> = request
+            .headers
+            .into_iter()
+            .filter(|header| !header.name.starts_with(':'))
+            .map(|header| (header.name.to_lowercase(), header.value))
+            .filter(|(name, _)| !IGNORED_HEADERS.contains(&name.as_str()))
+            .filter(|(name, _)| !import.strip_cookies || name != "cookie")
+            .filter(|(name, _)| {
+                !import.strip_auth
+                    || !matches!(name.as_str(), "authorization" | "proxy-authorization")
+            })
+            .collect();
+
+        if let Some(post_data) = &request.post_data {
+            let name = detail.name.clone().unwrap_or_default();
+
+            if let Some(warning) = body(&mut detail, post_data, &name) {
+                warnings.push(warning);
+            }
+
+            // Set when building the request from the body or form
+            if detail.body.is_some() || detail.form.is_some() {
+                headers.retain(|(name, _)| name != "content-type");
+            }
+        }
+
+        super::split_auth(&mut detail, &mut headers);
+
+        if !headers.is_empty() {
+            detail.headers = Some(headers.into_iter().collect());
+        }
+
+        let group = match groups.iter().position(|group| {
+            group.scheme == detail.scheme && group.host == detail.host && group.port == detail.port
+        }) {
+            Some(index) => &mut groups[index],
+            None => {
+                groups.push(Detail {
+                    name: Some(slug(detail.host.as_deref().unwrap_or_default())),
+                    scheme: detail.scheme.clone(),
+                    host: detail.host.clone(),
+                    port: detail.port,
+                    requests: Some(vec![]),
+                    ..Detail::new()
+                });
+
+                groups.last_mut().expect("just added a group")
+            }
+        };
+
+        detail.scheme = None;
+        detail.host = None;
+        detail.port = None;
+
+        let requests = group.requests.get_or_insert_with(Vec::new);
+
+        if !requests.contains(&detail) {
+            requests.push(detail);
+        }
+    }
+
+    Ok((groups, warnings))
+}
+
+/// Whether a request matches all of the filters given, each of which matches
+/// if any of its patterns do.
+fn included(url: &reqwest::Url, method: &str, import: &Import) -> bool {
+    let host = url.host_str().unwrap_or_default();
+    let host_port = url.port().map(|port| format!("{host}:{port}"));
+
+    let hosts = import.hosts.is_empty()
+        || import.hosts.iter().any(|pattern| {
+            glob(pattern, host) || host_port.as_deref().is_some_and(|h| glob(pattern, h))
+        });
+
+    let methods = import.methods.is_empty()
+        || import
+            .methods
+            .iter()
+            .any(|pattern| pattern.eq_ignore_ascii_case(method));
+
+    let urls = import.urls.is_empty()
+        || import
+            .urls
+            .iter()
+            .any(|pattern| glob(pattern, url.as_str()));
+
+    hosts && methods && urls
+}
+
+/// Sets the body or form of a request from HAR post data, or a warning if it
+/// can't be converted.
+fn body(detail: &mut Detail, post_data: &PostData, name: &str) -> Option<String> {
+    let content_type = post_data.mime_type.to_lowercase();
+    let text = post_data.text.as_deref().unwrap_or_default();
+
+    if content_type.contains("x-www-form-urlencoded") {
+        let form: Vec<(String, String)> = match post_data.params.is_empty() {
+            true => match serde_urlencoded::from_str(text) {
+                Ok(form) => form,
+                Err(_) => {
+                    return Some(format!(
+                        "'{name}' has invalid form data, which was skipped."
+                    ))
+                }
+            },
+            false => post_data
+                .params
+                .iter()
+                .map(|param| (param.name.clone(), param.value.clone().unwrap_or_default()))
+                .collect(),
+        };
+
+        detail.form = Some(form.into_iter().collect());
+
+        return None;
+    }
+
+    if text.is_empty() {
+        return None;
+    }
+
+    if !content_type.is_empty() && !content_type.contains("json") {
+        return Some(format!(
+            "'{name}' has a {content_type} body, but only JSON and form data bodies are supported."
+        ));
+    }
+
+    match serde_json::from_str(text) {
+        Ok(json) => {
+            detail.body = Some(json);
+            None
+        }
+        Err(_) => Some(format!(
+            "'{name}' has a body which isn't valid JSON, so it was skipped."
+        )),
+    }
+}
@@ -0,0 +1,475 @@
+use std::collections::{HashMap, HashSet};
+
+use super::slug;
+use crate::{
+    types::{AuthType, Detail},
+    variables,
+};
+
+const METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+/// Request for a request built from `detail`, in the `.http` format used by
+/// editor HTTP clients, preceded by a `###` separator.
+pub fn http(request: &reqwest::Request, detail: &Detail) -> String {
+    let mut url = request.url().clone();
+
+    // Basic auth is already in the built request's Authorization header
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url.set_fragment(None);
+
+    let name = detail.name.as_deref().unwrap_or_default();
+
+    let mut lines = vec![format!("### {name}"), format!("# @name {name}")];
+
+    if let Some(timeout) = detail.timeout {
+        lines.push(format!("# @timeout {timeout}"));
+    }
+
+    lines.push(format!("{} {}", request.method(), url));
+
+    // Requests are always built with a body, which is `null` unless one is given
+    let has_body = detail.body.is_some() || detail.form.is_some();
+
+    let user_content_type = detail
+        .headers
+        .iter()
+        .flatten()
+        .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+
+    let mut headers: Vec<(&str, String)> = request
+        .headers()
+        .iter()
+        .filter(|(name, _)| has_body || user_content_type || *name != "content-type")
+        .map(|(name, value)| {
+            (
+                name.as_str(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            )
+        })
+        .collect();
+
+    headers.sort();
+
+    for (name, value) in headers {
+        lines.push(format!("{name}: {value}"));
+    }
+
+    let body = match (&detail.body, request.body().and_then(|b| b.as_bytes())) {
+        (Some(json), _) => serde_json::to_string_pretty(json).ok(),
+        (None, Some(body)) if has_body => Some(String::from_utf8_lossy(body).to_string()),
+        _ => None,
+    };
+
+    if let Some(body) = body {
+        lines.push(String::new());
+        lines.push(body);
+    }
+
+    lines.join("\n")
+}
+
+/// Request definitions for each request in a `.http` or `.rest` file, as used
+/// by editor HTTP clients, along with warnings for anything which couldn't be
+/// converted.
+///
+/// Requests are separated by `###`, and named by a `# @name` comment or
+/// otherwise the text after the separator. File variables, e.g. `@host =
+/// example.com`, are defined on a group the requests are nested in.
+pub fn import(input: &str) -> Result<(Vec<Detail>, Vec<String>), String> {
+    let mut blocks: Vec<(Option<String>, Vec<&str>)> = vec![(None, vec![])];
+
+    for line in input.lines() {
+        match line.trim_start().strip_prefix("###") {
+            Some(title) => blocks.push((
+                Some(title.trim().to_string()).filter(|t| !t.is_empty()),
+                vec![],
+            )),
+            None => blocks
+                .last_mut()
+                .expect("blocks start with one")
+                .1
+                .push(line),
+        }
+    }
+
+    let mut importer = Importer {
+        variables: HashMap::new(),
+        warnings: vec![],
+    };
+
+    let mut requests = vec![];
+
+    for (title, lines) in &blocks {
+        if let Some(detail) = importer.block(title.as_deref(), lines)? {
+            requests.push(detail);
+        }
+    }
+
+    importer.check_variables(input);
+
+    let details = match importer.variables.is_empty() {
+        true => requests,
+        false => vec![Detail {
+            variables: Some(importer.variables),
+            requests: Some(requests),
+            ..Detail::new()
+        }],
+    };
+
+    Ok((details, importer.warnings))
+}
+
+struct Importer {
+    variables: HashMap<String, String>,
+    warnings: Vec<String>,
+}
+
+impl Importer {
+    /// Converts the lines between separators, which may only define
+    /// variables.
+    fn block(&mut self, title: Option<&str>, lines: &[&str]) -> Result<Option<Detail>, String> {
+        let mut lines = lines.iter().map(|line| line.trim_end()).peekable();
+        let mut name = None;
+        let mut timeout = None;
+
+        // Comments, directives and variables come before the request line
+        let request_line = loop {
+            let Some(line) = lines.next() else {
+                return Ok(None);
+            };
+
+            let line = line.trim_start();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(comment) = line.strip_prefix('#').or_else(|| line.strip_prefix("//")) {
+                let directive = comment.trim().strip_prefix('@').unwrap_or_default();
+                let (key, value) = directive.split_once([' ', '=']).unwrap_or((directive, ""));
+
+                match key {
+                    "name" => name = Some(value.trim().to_string()),
+                    "timeout" => timeout = value.trim().parse().ok(),
+                    _ => {}
+                }
+
+                continue;
+            }
+
+            if let Some((key, value)) = line.strip_prefix('@').and_then(|v| v.split_once('=')) {
+                self.variables
+                    .insert(key.trim().to_string(), value.trim().to_string());
+                continue;
+            }
+
+            break line;
+        };
+
+        let (method, mut url) = match request_line.split_once(char::is_whitespace) {
+            Some((method, url)) if METHODS.contains(&method) => {
+                (method.to_lowercase(), url.trim().to_string())
+            }
+            _ => (String::from("get"), request_line.to_string()),
+        };
+
+        // Query params may continue on the following lines
+        while let Some(line) = lines.next_if(|line| {
+            let line = line.trim_start();
+            line.starts_with('?') || line.starts_with('&')
+        }) {
+            url.push_str(line.trim());
+        }
+
+        if let Some((rest, version)) = url.rsplit_once(char::is_whitespace) {
+            if version.starts_with("HTTP/") {
+                url = rest.trim().to_string();
+            }
+        }
+
+        let mut headers: Vec<(String, String)> = vec![];
+
+        for line in lines.by_ref() {
+            let line = line.trim_start();
+
+            if line.is_empty() {
+                break;
+            }
+
+            if line.starts_with('#') || line.starts_with("//") {
+                continue;
+            }
+
+            match line.split_once(':') {
+                Some((name, value)) => {
+                    headers.push((name.trim().to_string(), value.trim().to_string()))
+                }
+                None => {
+                    return Err(format!(
+                        "Invalid header '{line}' in request '{request_line}'."
+                    ))
+                }
+            }
+        }
+
+        let name = name
+            .or(title.map(slug))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| slug(&format!("{method}_{}", path(&url))));
+
+        // Response handlers and redirects follow the body
+        let mut body = vec![];
+
+        for line in lines {
+            if line.starts_with('>') || line.starts_with("<>") {
+                self.warn(format!(
+                    "'{name}' has a response handler or redirect, which is not supported."
+                ));
+                break;
+            }
+
+            body.push(line);
+        }
+
+        let body = body.join("\n").trim().to_string();
+
+        let mut detail = self.url(&url, &mut headers, &method)?;
+
+        detail.name = Some(name.clone());
+        detail.timeout = timeout;
+
+        if !body.is_empty() {
+            self.body(&mut detail, &body, &headers, &name);
+        }
+
+        if detail.body.is_some() || detail.form.is_some() {
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+        }
+
+        basic_auth(&mut detail, &mut headers);
+        super::split_auth(&mut detail, &mut headers);
+
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-length"));
+
+        if !headers.is_empty() {
+            detail.headers = Some(headers.into_iter().collect());
+        }
+
+        Ok(Some(detail))
+    }
+
+    /// Splits a URL, which may use variables, into its host, resource and so
+    /// on. The host is taken from the `Host` header for URLs with only a path.
+    fn url(
+        &self,
+        url: &str,
+        headers: &mut Vec<(String, String)>,
+        method: &str,
+    ) -> Result<Detail, String> {
+        let invalid = |url: &str| format!("Invalid URL '{url}'.");
+
+        // Variables at the start of a URL may contain its scheme and host
+        let mut url = url.to_string();
+
+        for _ in 0..8 {
+            if !url.starts_with("{{") {
+                break;
+            }
+
+            let end = url.find("}}").ok_or_else(|| invalid(&url))?;
+            let name = url[2..end].trim();
+            let value = self.variables.get(name).ok_or_else(|| {
+                format!("Variable '{name}' at the start of URL '{url}' is not defined.")
+            })?;
+
+            url = format!("{value}{}", &url[end + 2..]);
+        }
+
+        let (scheme, rest) = match url.split_once("://") {
+            Some((scheme, rest)) => (scheme.to_lowercase(), rest.to_string()),
+            None => (String::from("http"), url.clone()),
+        };
+
+        let (authority, rest) = match rest.find(['/', '?', '#']) {
+            Some(index) => rest.split_at(index),
+            None => (rest.as_str(), ""),
+        };
+
+        let authority = match authority {
+            "" => {
+                let index = headers
+                    .iter()
+                    .position(|(name, _)| name.eq_ignore_ascii_case("host"))
+                    .ok_or_else(|| format!("URL '{url}' has no host."))?;
+
+                headers.remove(index).1
+            }
+            authority => authority.to_string(),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.ends_with(']') => {
+                let port = variables::render(port, &self.variables)
+                    .map_err(|name| format!("Variable '{name}' in URL '{url}' is not defined."))?;
+
+                (
+                    host.to_string(),
+                    Some(port.parse::<u16>().map_err(|_| invalid(&url))?),
+                )
+            }
+            _ => (authority, None),
+        };
+
+        let (rest, hash) = match rest.split_once('#') {
+            Some((rest, hash)) => (rest, Some(hash.to_string())),
+            None => (rest, None),
+        };
+
+        let (resource, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        let params: Vec<(String, String)> = serde_urlencoded::from_str(query)
+            .map_err(|e| format!("Invalid query in URL '{url}': {e}"))?;
+
+        Ok(Detail {
+            scheme: Some(scheme).filter(|scheme| scheme != "http"),
+            host: Some(host),
+            port,
+            resource: Some(match resource {
+                "" => String::from("/"),
+                resource => resource.to_string(),
+            }),
+            hash,
+            params: Some(params.into_iter().collect()).filter(|p: &HashMap<_, _>| !p.is_empty()),
+            method: Some(method.to_string()).filter(|method| method != "get"),
+            ..Detail::new()
+        })
+    }
+
+    fn body(&mut self, detail: &mut Detail, body: &str, headers: &[(String, String)], name: &str) {
+        if body.starts_with('<') {
+            self.warn(format!(
+                "'{name}' has a body read from a file, which is not supported."
+            ));
+            return;
+        }
+
+        let content_type = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.to_lowercase());
+
+        match content_type.as_deref() {
+            Some(t) if t.contains("x-www-form-urlencoded") => {
+                // Fields may be split over several lines
+                let data: String = body.lines().map(str::trim).collect();
+
+                match serde_urlencoded::from_str::<Vec<(String, String)>>(&data) {
+                    Ok(form) => detail.form = Some(form.into_iter().collect()),
+                    Err(_) => self.warn(format!(
+                        "'{name}' has invalid form data, which was skipped."
+                    )),
+                }
+            }
+            Some(t) if !t.contains("json") => self.warn(format!(
+                "'{name}' has a {t} body, but only JSON and form data bodies are supported."
+            )),
+            _ => {
+                // Variables used as numbers or objects aren't valid JSON until
+                // replaced with their values
+                let json = serde_json::from_str(body).ok().or_else(|| {
+                    let rendered = variables::render(body, &self.variables).ok()?;
+                    serde_json::from_str(&rendered).ok()
+                });
+
+                match json {
+                    Some(json) => detail.body = Some(json),
+                    None => self.warn(format!(
+                        "'{name}' has a body which isn't valid JSON, so it was skipped."
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Warns about variables which aren't defined in the file, such as those
+    /// from environment files, along with any referring to other requests or
+    /// generating values, which are not supported.
+    fn check_variables(&mut self, input: &str) {
+        let mut checked = HashSet::new();
+        let mut rest = input;
+
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+
+            let name = rest[start + 2..start + end].trim().to_string();
+
+            rest = &rest[start + end + 2..];
+
+            if self.variables.contains_key(&name) || !checked.insert(name.clone()) {
+                continue;
+            }
+
+            let warning = match name.split('.').nth(1) {
+                _ if name.starts_with('$') => {
+                    format!("Variable '{name}' generates a value, which is not supported.")
+                }
+                Some("response" | "request") => {
+                    format!("Variable '{name}' refers to another request, which is not supported.")
+                }
+                _ => format!("Variable '{name}' is not defined in the file, so must be added."),
+            };
+
+            self.warn(warning);
+        }
+    }
+
+    fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+}
+
+/// Moves basic credentials which editor HTTP clients allow to be given
+/// unencoded, e.g. `Basic user:pass` or `Basic user pass`, to `auth`.
+fn basic_auth(detail: &mut Detail, headers: &mut Vec<(String, String)>) {
+    let Some(index) = headers.iter().position(|(name, value)| {
+        name.eq_ignore_ascii_case("authorization")
+            && value
+                .get(..6)
+                .is_some_and(|s| s.eq_ignore_ascii_case("basic "))
+    }) else {
+        return;
+    };
+
+    let credentials = headers[index].1[6..].trim();
+
+    let Some((username, password)) = credentials
+        .split_once(':')
+        .or_else(|| credentials.split_once(' '))
+    else {
+        return;
+    };
+
+    detail.auth = Some(AuthType::Basic {
+        username: username.trim().to_string(),
+        password: password.trim().to_string(),
+    });
+
+    headers.remove(index);
+}
+
+/// Path of a URL, which may use variables, for naming requests.
+fn path(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+
+    let rest = match rest.starts_with('/') {
+        true => rest,
+        false => rest.find('/').map_or("", |index| &rest[index..]),
+    };
+
+    rest.split(['?', '#']).next().unwrap_or_default()
+}
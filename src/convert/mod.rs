@@ -0,0 +1,132 @@
+use std::collections::{HashMap, HashSet};
+
+use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine};
+
+use crate::types::{AuthType, Detail};
+
+pub mod curl;
+pub mod har;
+pub mod http;
+pub mod openapi;
+pub mod postman;
+
+/// Request definition for a URL, split into its host, port, resource and so
+/// on, and named after its method and path.
+pub(crate) fn detail(url: &reqwest::Url, method: &str) -> Detail {
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+
+    Detail {
+        name: Some(name(method, url)),
+        scheme: Some(url.scheme().to_string()).filter(|scheme| scheme != "http"),
+        host: url.host_str().map(String::from),
+        port: url.port(),
+        resource: Some(url.path().to_string()),
+        hash: url.fragment().map(String::from),
+        params: Some(params).filter(|params| !params.is_empty()),
+        method: Some(method.to_lowercase()).filter(|method| method != "get"),
+        ..Detail::new()
+    }
+}
+
+/// Name for a request from its method and path, e.g. `post_api_users`.
+fn name(method: &str, url: &reqwest::Url) -> String {
+    let path = match url.path().trim_matches('/') {
+        "" => url.host_str().unwrap_or_default(),
+        path => path,
+    };
+
+    slug(&format!("{method}_{path}"))
+}
+
+/// Name usable on the command line, e.g. `get_user_by_id` for 'Get user by ID'.
+pub(crate) fn slug(value: &str) -> String {
+    let slug: String = value
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+
+    slug.split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Value as text, without quotes around strings.
+pub(crate) fn text(value: Option<&serde_json::Value>) -> String {
+    match value {
+        Some(serde_json::Value::String(value)) => value.clone(),
+        Some(serde_json::Value::Null) | None => String::new(),
+        Some(value) => value.to_string(),
+    }
+}
+
+/// Moves bearer and basic credentials in an `Authorization` header to `auth`.
+pub(crate) fn split_auth(detail: &mut Detail, headers: &mut Vec<(String, String)>) {
+    let Some(index) = headers
+        .iter()
+        .position(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+    else {
+        return;
+    };
+
+    let (scheme, credentials) = headers[index].1.split_once(' ').unwrap_or_default();
+
+    let auth = match scheme.to_lowercase().as_str() {
+        "bearer" => Some(AuthType::Bearer {
+            token: credentials.trim().to_string(),
+        }),
+        // Padding is optional, as it's often left out
+        "basic" => STANDARD_NO_PAD
+            .decode(credentials.trim().trim_end_matches('='))
+            .ok()
+            .and_then(|decoded| String::from_utf8(decoded).ok())
+            .and_then(|decoded| {
+                let (username, password) = decoded.split_once(':')?;
+
+                Some(AuthType::Basic {
+                    username: username.to_string(),
+                    password: password.to_string(),
+                })
+            }),
+        _ => None,
+    };
+
+    if auth.is_some() {
+        detail.auth = auth;
+        headers.remove(index);
+    }
+}
+
+/// Suffixes names which are already taken, whether by other imported
+/// requests, including nested requests, or those in `taken`, e.g.
+/// `get_users_2`, so every request can be run by name.
+pub(crate) fn unique_names(details: Vec<Detail>, taken: &HashSet<String>) -> Vec<Detail> {
+    let mut taken = taken.clone();
+
+    rename(details, &mut taken)
+}
+
+fn rename(details: Vec<Detail>, taken: &mut HashSet<String>) -> Vec<Detail> {
+    details
+        .into_iter()
+        .map(|mut detail| {
+            if let Some(name) = &detail.name {
+                let mut unique = name.clone();
+                let mut n = 1;
+
+                while taken.contains(&unique) {
+                    n += 1;
+                    unique = format!("{name}_{n}");
+                }
+
+                taken.insert(unique.clone());
+                detail.name = Some(unique);
+            }
+
+            detail.requests = detail.requests.map(|requests| rename(requests, taken));
+
+            detail
+        })
+        .collect()
+}
@@ -0,0 +1,602 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value};
+
+use super::{slug, text};
+use crate::types::{AuthType, Detail};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// How many `$ref`s are followed in a row, in case they refer to each other.
+const MAX_REFERENCES: usize = 8;
+
+/// Request definitions for each operation in an OpenAPI 3 specification,
+/// along with warnings for anything which couldn't be converted.
+///
+/// Requests are nested in a group named after the API, which is sent to the
+/// first server, with a group for each tag. Path parameters become variables,
+/// and params and bodies are filled in from examples or made up from schemas.
+pub fn import(input: &str) -> Result<(Vec<Detail>, Vec<String>), String> {
+    let spec: serde_yaml::Value = serde_yaml::from_str(input)
+        .map_err(|e| format!("Failed to parse OpenAPI specification: {e}"))?;
+
+    let spec = json(spec);
+
+    match spec.get("openapi").and_then(Value::as_str) {
+        Some(version) if version.starts_with("3.") => {}
+        Some(version) => {
+            return Err(format!(
+                "Unsupported OpenAPI version '{version}', expected 3.x."
+            ))
+        }
+        None => return Err(String::from("Not an OpenAPI 3 specification.")),
+    }
+
+    let mut converter = Converter {
+        spec: &spec,
+        warnings: vec![],
+        variables: HashMap::new(),
+    };
+
+    let title = text(spec.pointer("/info/title"));
+
+    let mut api = Detail {
+        name: Some(
+            Some(slug(&title))
+                .filter(|name| !name.is_empty())
+                .unwrap_or(String::from("api")),
+        ),
+        ..Detail::new()
+    };
+
+    let base = converter.server(&mut api);
+
+    let global = converter.security(spec.get("security"), "the API");
+
+    if let Security::Auth(auth) = &global {
+        api.auth = Some(auth.clone());
+    }
+
+    // Groups in the order tags are declared, followed by any undeclared ones
+    let mut groups: Vec<(String, Vec<Detail>)> = spec
+        .get("tags")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|tag| (text(tag.get("name")), vec![]))
+        .collect();
+
+    let mut untagged = vec![];
+
+    let paths = spec.get("paths").and_then(Value::as_object);
+
+    for (path, item) in paths.into_iter().flatten() {
+        let item = converter.resolve(item);
+
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+
+            let detail = converter.operation(path, method, item, operation, &base, &global);
+
+            let tag = operation
+                .pointer("/tags/0")
+                .and_then(Value::as_str)
+                .map(String::from);
+
+            match tag {
+                Some(tag) => match groups.iter_mut().find(|(name, _)| *name == tag) {
+                    Some((_, requests)) => requests.push(detail),
+                    None => groups.push((tag, vec![detail])),
+                },
+                None => untagged.push(detail),
+            }
+        }
+    }
+
+    let mut requests: Vec<Detail> = groups
+        .into_iter()
+        .filter(|(_, requests)| !requests.is_empty())
+        .map(|(tag, requests)| Detail {
+            name: Some(slug(&tag)),
+            requests: Some(requests),
+            ..Detail::new()
+        })
+        .collect();
+
+    requests.extend(untagged);
+
+    if !converter.variables.is_empty() {
+        api.variables = Some(converter.variables);
+    }
+
+    api.requests = Some(requests);
+
+    Ok((vec![api], converter.warnings))
+}
+
+/// How an operation is authenticated.
+#[derive(Clone, PartialEq)]
+enum Security {
+    None,
+    Auth(AuthType),
+    Header(String, String),
+    Param(String, String),
+}
+
+struct Converter<'a> {
+    spec: &'a Value,
+    warnings: Vec<String>,
+    /// Variables for credentials, defined on the API's group to be filled in.
+    variables: HashMap<String, String>,
+}
+
+impl<'a> Converter<'a> {
+    /// Sets the host, scheme and port of the first server on the API's group,
+    /// returning its base path.
+    fn server(&mut self, api: &mut Detail) -> String {
+        let localhost = Some(String::from("localhost"));
+
+        let Some(server) = self.spec.pointer("/servers/0") else {
+            self.warn(String::from(
+                "No servers are defined, so requests are sent to localhost.",
+            ));
+            api.host = localhost;
+            return String::new();
+        };
+
+        let mut url = text(server.get("url"));
+
+        let variables = server.get("variables").and_then(Value::as_object);
+
+        for (name, variable) in variables.into_iter().flatten() {
+            url = url.replace(&format!("{{{name}}}"), &text(variable.get("default")));
+        }
+
+        if !url.contains("://") {
+            self.warn(format!(
+                "Server URL '{url}' is relative, so requests are sent to localhost."
+            ));
+            api.host = localhost;
+            return url.trim_end_matches('/').to_string();
+        }
+
+        let Ok(url) = reqwest::Url::parse(&url) else {
+            self.warn(format!(
+                "Server URL '{url}' is invalid, so requests are sent to localhost."
+            ));
+            api.host = localhost;
+            return String::new();
+        };
+
+        api.scheme = Some(url.scheme().to_string()).filter(|scheme| scheme != "http");
+        api.host = url.host_str().map(String::from);
+        api.port = url.port();
+
+        url.path().trim_end_matches('/').to_string()
+    }
+
+    fn operation(
+        &mut self,
+        path: &str,
+        method: &str,
+        item: &Value,
+        operation: &Value,
+        base: &str,
+        global: &Security,
+    ) -> Detail {
+        let name = match operation.get("operationId").and_then(Value::as_str) {
+            Some(id) => slug(&snake_case(id)),
+            None => slug(&format!("{method}_{path}")),
+        };
+
+        let mut variables = HashMap::new();
+        let mut params = HashMap::new();
+        let mut headers = HashMap::new();
+
+        // Parameters of an operation override those shared by its path
+        let mut parameters: Vec<&Value> = vec![];
+
+        for parameter in [item.get("parameters"), operation.get("parameters")]
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_array)
+            .flatten()
+        {
+            let parameter = self.resolve(parameter);
+            let key = |p: &Value| (text(p.get("name")), text(p.get("in")));
+
+            parameters.retain(|p| key(p) != key(parameter));
+            parameters.push(parameter);
+        }
+
+        for parameter in parameters {
+            let key = text(parameter.get("name"));
+            let required = parameter.get("required") == Some(&Value::Bool(true));
+
+            let example = self.example(parameter);
+            let value = || {
+                text(Some(&example.clone().unwrap_or_else(|| {
+                    self.schema_example(parameter.get("schema"), &[])
+                        .unwrap_or_default()
+                })))
+            };
+
+            match parameter.get("in").and_then(Value::as_str) {
+                Some("path") => {
+                    variables.insert(key, value());
+                }
+                Some("query") if required || example.is_some() => {
+                    params.insert(key, value());
+                }
+                // Set from the body and auth instead
+                Some("header")
+                    if ["accept", "content-type", "authorization"]
+                        .contains(&key.to_lowercase().as_str()) => {}
+                Some("header") if required || example.is_some() => {
+                    headers.insert(key, value());
+                }
+                Some("cookie") => self.warn(format!(
+                    "'{name}' has a '{key}' cookie parameter, but cookies are not supported."
+                )),
+                _ => {}
+            }
+        }
+
+        // Placeholders without a path parameter are left to be filled in
+        for placeholder in placeholders(path) {
+            if !variables.contains_key(placeholder) {
+                self.warn(format!(
+                    "'{name}' has no '{placeholder}' path parameter, so it's an empty variable to be filled in."
+                ));
+                variables.insert(placeholder.to_string(), String::new());
+            }
+        }
+
+        let mut detail = Detail {
+            name: Some(name.clone()),
+            resource: Some(format!(
+                "{base}{}",
+                path.replace('{', "{{").replace('}', "}}")
+            )),
+            method: Some(method.to_string()).filter(|method| method != "get"),
+            ..Detail::new()
+        };
+
+        if let Some(body) = operation.get("requestBody") {
+            self.body(&mut detail, self.resolve(body), &name);
+        }
+
+        let security = match operation.get("security") {
+            Some(security) => self.security(Some(security), &format!("'{name}'")),
+            None => global.clone(),
+        };
+
+        match security {
+            Security::Auth(auth) if Security::Auth(auth.clone()) != *global => {
+                detail.auth = Some(auth)
+            }
+            Security::None if matches!(global, Security::Auth(_)) => self.warn(format!(
+                "'{name}' doesn't need auth, but will inherit auth from the API."
+            )),
+            Security::Header(key, value) => {
+                headers.insert(key, value);
+            }
+            Security::Param(key, value) => {
+                params.insert(key, value);
+            }
+            _ => {}
+        }
+
+        detail.variables = Some(variables).filter(|v| !v.is_empty());
+        detail.params = Some(params).filter(|p| !p.is_empty());
+        detail.headers = Some(headers).filter(|h| !h.is_empty());
+
+        detail
+    }
+
+    fn body(&mut self, detail: &mut Detail, body: &Value, name: &str) {
+        let Some(content) = body.get("content").and_then(Value::as_object) else {
+            return;
+        };
+
+        let json = content
+            .iter()
+            .find(|(media_type, _)| media_type.ends_with("/json") || media_type.contains("+json"));
+
+        if let Some((_, media)) = json {
+            detail.body = Some(self.media_example(media));
+            return;
+        }
+
+        if let Some(media) = content.get("application/x-www-form-urlencoded") {
+            if let Value::Object(fields) = self.media_example(media) {
+                detail.form = Some(
+                    fields
+                        .iter()
+                        .map(|(key, value)| (key.clone(), text(Some(value))))
+                        .collect(),
+                );
+            }
+            return;
+        }
+
+        if let Some(media_type) = content.keys().next() {
+            self.warn(format!(
+                "'{name}' has a {media_type} body, but only JSON and form data bodies are supported."
+            ));
+        }
+    }
+
+    fn media_example(&self, media: &Value) -> Value {
+        self.example(media).unwrap_or_else(|| {
+            self.schema_example(media.get("schema"), &[])
+                .unwrap_or_default()
+        })
+    }
+
+    /// Example given for a parameter or media type, if any.
+    fn example(&self, value: &Value) -> Option<Value> {
+        if let Some(example) = value.get("example") {
+            return Some(example.clone());
+        }
+
+        let examples = value.get("examples").and_then(Value::as_object)?;
+
+        examples
+            .values()
+            .next()
+            .and_then(|example| self.resolve(example).get("value"))
+            .cloned()
+    }
+
+    /// Example made up from a schema, preferring any examples, defaults or
+    /// allowed values it gives, or `None` if the schema refers back to one
+    /// it's nested in.
+    fn schema_example(&self, schema: Option<&Value>, seen: &[String]) -> Option<Value> {
+        let Some(schema) = schema else {
+            return Some(Value::Null);
+        };
+
+        let mut seen = seen.to_vec();
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            if seen.iter().any(|seen| seen == reference) {
+                return None;
+            }
+
+            seen.push(reference.to_string());
+        }
+
+        let schema = self.resolve(schema);
+
+        for key in ["example", "default", "const"] {
+            if let Some(example) = schema.get(key) {
+                return Some(example.clone());
+            }
+        }
+
+        for key in ["examples", "enum"] {
+            if let Some(example) = schema.get(key).and_then(|values| values.get(0)) {
+                return Some(example.clone());
+            }
+        }
+
+        if let Some(Value::Array(schemas)) = schema.get("allOf") {
+            let mut merged = Map::new();
+
+            for schema in schemas {
+                match self.schema_example(Some(schema), &seen)? {
+                    Value::Object(example) => merged.extend(example),
+                    example if schemas.len() == 1 => return Some(example),
+                    _ => {}
+                }
+            }
+
+            return Some(Value::Object(merged));
+        }
+
+        for key in ["oneOf", "anyOf"] {
+            if let Some(schema) = schema.get(key).and_then(|schemas| schemas.get(0)) {
+                return self.schema_example(Some(schema), &seen);
+            }
+        }
+
+        let kind = match schema.get("type") {
+            Some(Value::String(kind)) => kind.as_str(),
+            // OpenAPI 3.1 allows several types, e.g. [string, "null"]
+            Some(Value::Array(kinds)) => kinds
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|kind| *kind != "null")
+                .unwrap_or("null"),
+            _ if schema.get("properties").is_some() => "object",
+            _ if schema.get("items").is_some() => "array",
+            _ => "",
+        };
+
+        let example = match kind {
+            // Properties which refer back to a parent are left out
+            "object" => Value::Object(
+                schema
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(key, property)| {
+                        Some((key.clone(), self.schema_example(Some(property), &seen)?))
+                    })
+                    .collect(),
+            ),
+            "array" => Value::Array(
+                self.schema_example(schema.get("items"), &seen)
+                    .into_iter()
+                    .collect(),
+            ),
+            "string" => json!(match schema.get("format").and_then(Value::as_str) {
+                Some("date-time") => "2024-01-01T00:00:00Z",
+                Some("date") => "2024-01-01",
+                Some("email") => "user@example.com",
+                Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                Some("uri" | "url") => "https://example.com",
+                _ => "string",
+            }),
+            "integer" | "number" => json!(1),
+            "boolean" => json!(true),
+            _ => Value::Null,
+        };
+
+        Some(example)
+    }
+
+    /// Converts the first security requirement, using variables for the
+    /// credentials so they can be filled in.
+    fn security(&mut self, requirements: Option<&Value>, of: &str) -> Security {
+        let Some(requirement) = requirements
+            .and_then(|requirements| requirements.get(0))
+            .and_then(Value::as_object)
+            .and_then(|requirement| requirement.keys().next())
+        else {
+            return Security::None;
+        };
+
+        let Some(scheme) = self
+            .spec
+            .pointer(&format!("/components/securitySchemes/{requirement}"))
+            .map(|scheme| self.resolve(scheme))
+        else {
+            self.warn(format!(
+                "Security scheme '{requirement}' used by {of} is not defined."
+            ));
+            return Security::None;
+        };
+
+        let kind = text(scheme.get("type"));
+        let http = text(scheme.get("scheme")).to_lowercase();
+
+        match (kind.as_str(), http.as_str()) {
+            ("http", "bearer") | ("oauth2" | "openIdConnect", _) => {
+                Security::Auth(AuthType::Bearer {
+                    token: self.variable("token"),
+                })
+            }
+            ("http", "basic") => Security::Auth(AuthType::Basic {
+                username: self.variable("username"),
+                password: self.variable("password"),
+            }),
+            ("apiKey", _) => {
+                let key = text(scheme.get("name"));
+                let value = self.variable("api_key");
+
+                match scheme.get("in").and_then(Value::as_str) {
+                    Some("header") => Security::Header(key, value),
+                    Some("query") => Security::Param(key, value),
+                    _ => {
+                        self.warn(format!(
+                            "Security scheme '{requirement}' used by {of} sends an API key as a cookie, which is not supported."
+                        ));
+                        Security::None
+                    }
+                }
+            }
+            _ => {
+                self.warn(format!(
+                    "Security scheme '{requirement}' used by {of} is not supported."
+                ));
+                Security::None
+            }
+        }
+    }
+
+    /// Reference to a variable, which is defined empty to be filled in.
+    fn variable(&mut self, name: &str) -> String {
+        self.variables.entry(name.to_string()).or_default();
+
+        format!("{{{{{name}}}}}")
+    }
+
+    /// Follows `$ref`s to components, or anything else in the specification.
+    fn resolve(&self, mut value: &'a Value) -> &'a Value {
+        for _ in 0..MAX_REFERENCES {
+            let Some(pointer) = value
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix('#'))
+            else {
+                break;
+            };
+
+            match self.spec.pointer(pointer) {
+                Some(target) => value = target,
+                None => break,
+            }
+        }
+
+        value
+    }
+
+    fn warn(&mut self, warning: String) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+}
+
+/// Splits camel case, e.g. `getUserById` to `get_User_By_Id`.
+fn snake_case(value: &str) -> String {
+    let mut snake = String::new();
+    let mut previous: Option<char> = None;
+
+    for c in value.chars() {
+        if c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_numeric()) {
+            snake.push('_');
+        }
+
+        snake.push(c);
+        previous = Some(c);
+    }
+
+    snake
+}
+
+/// Converts YAML to JSON, which doesn't have non-string keys such as the
+/// status codes of responses.
+fn json(value: serde_yaml::Value) -> Value {
+    match value {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(value) => Value::Bool(value),
+        serde_yaml::Value::Number(number) => match (number.as_i64(), number.as_u64()) {
+            (Some(number), _) => json!(number),
+            (_, Some(number)) => json!(number),
+            _ => number.as_f64().map(Value::from).unwrap_or_default(),
+        },
+        serde_yaml::Value::String(value) => Value::String(value),
+        serde_yaml::Value::Sequence(values) => Value::Array(values.into_iter().map(json).collect()),
+        serde_yaml::Value::Mapping(mapping) => Value::Object(
+            mapping
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        serde_yaml::Value::String(key) => key,
+                        key => text(Some(&json(key))),
+                    };
+
+                    (key, json(value))
+                })
+                .collect(),
+        ),
+        serde_yaml::Value::Tagged(tagged) => json(tagged.value),
+    }
+}
+
+/// Names of the `{placeholder}`s in a path template.
+fn placeholders(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}'))
+        .map(|(name, _)| name)
+        .collect()
+}
@@ -0,0 +1,630 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::text;
+use crate::{
+    types::{AuthType, Detail, Unset},
+    variables,
+};
+
+#[derive(Deserialize)]
+struct Collection {
+    info: Info,
+    #[serde(default)]
+    item: Vec<Item>,
+    auth: Option<Auth>,
+    #[serde(default)]
+    variable: Vec<KeyValue>,
+    #[serde(default)]
+    event: Vec<Event>,
+}
+
+#[derive(Deserialize)]
+struct Info {
+    name: String,
+    schema: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Item {
+    name: String,
+    item: Option<Vec<Item>>,
+    request: Option<Request>,
+    auth: Option<Auth>,
+    #[serde(default)]
+    event: Vec<Event>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Request {
+    Url(String),
+    Request(Box<Definition>),
+}
+
+#[derive(Deserialize)]
+struct Definition {
+    method: Option<String>,
+    #[serde(default)]
+    header: Vec<KeyValue>,
+    url: Option<Url>,
+    auth: Option<Auth>,
+    body: Option<Body>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Url {
+    Raw(String),
+    Parts {
+        raw: Option<String>,
+        protocol: Option<String>,
+        host: Option<Value>,
+        port: Option<String>,
+        #[serde(default)]
+        path: Vec<Value>,
+        #[serde(default)]
+        query: Vec<KeyValue>,
+        hash: Option<String>,
+    },
+}
+
+#[derive(Deserialize)]
+struct Auth {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(flatten)]
+    settings: HashMap<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct Body {
+    mode: Option<String>,
+    raw: Option<String>,
+    #[serde(default)]
+    urlencoded: Vec<KeyValue>,
+    graphql: Option<GraphQl>,
+    options: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct GraphQl {
+    query: Option<String>,
+    variables: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct KeyValue {
+    key: Option<String>,
+    value: Option<Value>,
+    #[serde(default)]
+    disabled: bool,
+}
+
+#[derive(Deserialize)]
+struct Event {
+    listen: Option<String>,
+    script: Option<Value>,
+}
+
+/// Request definitions for a Postman v2.1 collection, nested in a group named
+/// after the collection, along with warnings for anything which couldn't be
+/// converted.
+///
+/// Folders become groups of nested requests, and collection variables are
+/// replaced with their values. Variables which aren't defined are added to
+/// the group as empty variables, to be filled in.
+pub fn import(input: &str) -> Result<(Vec<Detail>, Vec<String>), String> {
+    let collection: Collection = serde_json::from_str(input)
+        .map_err(|e| format!("Failed to parse Postman collection: {e}"))?;
+
+    if let Some(schema) = &collection.info.schema {
+        if !schema.contains("v2.1") && !schema.contains("v2.0") {
+            return Err(format!(
+                "Unsupported Postman collection schema '{schema}', expected v2.1."
+            ));
+        }
+    }
+
+    let mut converter = Converter {
+        variables: collection
+            .variable
+            .iter()
+            .filter(|v| !v.disabled)
+            .filter_map(|v| Some((v.key.clone()?, text(v.value.as_ref()))))
+            .collect(),
+        warnings: vec![],
+        undefined: HashSet::new(),
+    };
+
+    let name = &collection.info.name;
+
+    converter.events(&collection.event, name);
+
+    let auth = converter.auth(collection.auth.as_ref(), None, name);
+
+    let requests = collection
+        .item
+        .iter()
+        .filter_map(|item| converter.item(item, collection.auth.as_ref(), name))
+        .collect();
+
+    let mut group = Detail {
+        name: Some(super::slug(name)),
+        auth,
+        requests: Some(requests),
+        ..Detail::new()
+    };
+
+    converter.api_key(&mut group, collection.auth.as_ref(), None);
+
+    let undefined: HashMap<String, String> = converter
+        .undefined
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| variables::is_name(name))
+        .map(|name| (name.to_string(), String::new()))
+        .collect();
+
+    group.variables = Some(undefined).filter(|undefined| !undefined.is_empty());
+
+    Ok((vec![group], converter.warnings))
+}
+
+struct Converter {
+    variables: HashMap<String, String>,
+    warnings: Vec<String>,
+    undefined: HashSet<String>,
+}
+
+impl Converter {
+    /// Converts a folder or request, given the auth it inherits.
+    fn item(&mut self, item: &Item, inherited: Option<&Auth>, parent: &str) -> Option<Detail> {
+        let path = format!("{parent} / {}", item.name);
+
+        self.events(&item.event, &path);
+
+        if let Some(items) = &item.item {
+            let auth = self.auth(item.auth.as_ref(), inherited, &path);
+
+            let mut detail = Detail {
+                name: Some(super::slug(&item.name)),
+                auth,
+                ..Detail::new()
+            };
+
+            self.api_key(&mut detail, item.auth.as_ref(), inherited);
+
+            let inherited = own(item.auth.as_ref()).or(inherited);
+
+            detail.requests = Some(
+                items
+                    .iter()
+                    .filter_map(|item| self.item(item, inherited, &path))
+                    .collect(),
+            );
+
+            return Some(detail);
+        }
+
+        let (method, headers, url, auth, body) = match item.request.as_ref()? {
+            Request::Url(url) => (None, &vec![], Some(Url::Raw(url.clone())), None, None),
+            Request::Request(request) => (
+                request.method.clone(),
+                &request.header,
+                request.url.as_ref().map(|url| self.url(url)).map(Url::Raw),
+                request.auth.as_ref(),
+                request.body.as_ref(),
+            ),
+        };
+
+        let method = method.unwrap_or(String::from("GET")).to_lowercase();
+
+        let Some(Url::Raw(url)) = url else {
+            self.warn(format!("'{path}' has no URL and was skipped."));
+            return None;
+        };
+
+        let url = self.substitute(&url);
+
+        let url = match url.contains("://") {
+            true => url,
+            false => format!("http://{url}"),
+        };
+
+        // Variables left in the URL are swapped for tokens while it's parsed,
+        // so they aren't percent-encoded, or lowercased in its host
+        let (masked, placeholders) = mask(&url);
+
+        let Ok(url) = reqwest::Url::parse(&masked) else {
+            self.warn(format!(
+                "'{path}' has an invalid URL '{url}' and was skipped."
+            ));
+            return None;
+        };
+
+        let mut headers: Vec<(String, String)> = headers
+            .iter()
+            .filter(|header| !header.disabled)
+            .filter_map(|header| {
+                Some((
+                    header.key.clone()?,
+                    self.substitute(&text(header.value.as_ref())),
+                ))
+            })
+            .collect();
+
+        let mut detail = unmask(super::detail(&url, &method), &placeholders);
+
+        detail.name = Some(super::slug(&item.name));
+        detail.auth = self.auth(auth, inherited, &path);
+
+        if let Some(body) = body {
+            self.body(&mut detail, body, &headers, &path);
+        }
+
+        if detail.body.is_some() || detail.form.is_some() {
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+        }
+
+        super::split_auth(&mut detail, &mut headers);
+
+        if !headers.is_empty() {
+            detail.headers = Some(headers.into_iter().collect());
+        }
+
+        self.api_key(&mut detail, auth, inherited);
+
+        Some(detail)
+    }
+
+    /// Raw URL, or one put together from its parts if there isn't one.
+    fn url(&self, url: &Url) -> String {
+        match url {
+            Url::Raw(raw) => raw.clone(),
+            Url::Parts { raw: Some(raw), .. } => raw.clone(),
+            Url::Parts {
+                raw: None,
+                protocol,
+                host,
+                port,
+                path,
+                query,
+                hash,
+            } => {
+                let host = match host {
+                    Some(Value::Array(parts)) => parts
+                        .iter()
+                        .map(|part| text(Some(part)))
+                        .collect::<Vec<_>>()
+                        .join("."),
+                    host => text(host.as_ref()),
+                };
+
+                let mut url = format!("{}://{}", protocol.as_deref().unwrap_or("http"), host);
+
+                if let Some(port) = port {
+                    url.push_str(&format!(":{port}"));
+                }
+
+                for segment in path {
+                    let segment = match segment {
+                        Value::Object(segment) => text(segment.get("value")),
+                        segment => text(Some(segment)),
+                    };
+
+                    url.push_str(&format!("/{segment}"));
+                }
+
+                let query: Vec<String> = query
+                    .iter()
+                    .filter(|param| !param.disabled)
+                    .filter_map(|param| {
+                        Some(format!(
+                            "{}={}",
+                            param.key.as_ref()?,
+                            text(param.value.as_ref())
+                        ))
+                    })
+                    .collect();
+
+                if !query.is_empty() {
+                    url.push_str(&format!("?{}", query.join("&")));
+                }
+
+                if let Some(hash) = hash {
+                    url.push_str(&format!("#{hash}"));
+                }
+
+                url
+            }
+        }
+    }
+
+    /// Converts bearer or basic auth, which is inherited by nested requests
+    /// in the same way in both Postman and corkscrew.
+    fn auth(
+        &mut self,
+        auth: Option<&Auth>,
+        inherited: Option<&Auth>,
+        path: &str,
+    ) -> Option<AuthType> {
+        let auth = auth?;
+
+        match auth.kind.as_str() {
+            "bearer" => Some(AuthType::Bearer {
+                token: self.setting(auth, "token").unwrap_or_default(),
+            }),
+            "basic" => Some(AuthType::Basic {
+                username: self.setting(auth, "username").unwrap_or_default(),
+                password: self.setting(auth, "password").unwrap_or_default(),
+            }),
+            "noauth" => {
+                if inherited.is_some_and(|inherited| {
+                    !["noauth", "apikey"].contains(&inherited.kind.as_str())
+                }) {
+                    self.warn(format!(
+                        "'{path}' has no auth, but will inherit auth from its parent."
+                    ));
+                }
+
+                None
+            }
+            "apikey" | "inherit" => None,
+            kind => {
+                self.warn(format!("'{path}' uses unsupported '{kind}' auth."));
+                None
+            }
+        }
+    }
+
+    /// Adds an API key to the headers or params of the folder or request which
+    /// sets it, for those nested in it to inherit. A folder or request with
+    /// other auth of its own unsets the API key it would inherit, as its auth
+    /// replaces it.
+    fn api_key(&mut self, detail: &mut Detail, auth: Option<&Auth>, inherited: Option<&Auth>) {
+        let Some(auth) = own(auth) else {
+            return;
+        };
+
+        let api_key = self.key(auth);
+
+        if let Some((query, key, _)) = inherited.and_then(|inherited| self.key(inherited)) {
+            if api_key.as_ref().map(|(query, key, _)| (*query, key)) != Some((query, &key)) {
+                let unset = detail.unset.get_or_insert_with(Unset::default);
+
+                match query {
+                    true => unset.params.get_or_insert_with(Vec::new).push(key),
+                    false => unset.headers.get_or_insert_with(Vec::new).push(key),
+                }
+            }
+        }
+
+        if let Some((query, key, value)) = api_key {
+            match query {
+                true => detail.params.get_or_insert_with(HashMap::new),
+                false => detail.headers.get_or_insert_with(HashMap::new),
+            }
+            .insert(key, value);
+        }
+    }
+
+    /// Whether an API key goes in the query, along with its name and value.
+    fn key(&mut self, auth: &Auth) -> Option<(bool, String, String)> {
+        if auth.kind != "apikey" {
+            return None;
+        }
+
+        Some((
+            self.setting(auth, "in").as_deref() == Some("query"),
+            self.setting(auth, "key").unwrap_or_default(),
+            self.setting(auth, "value").unwrap_or_default(),
+        ))
+    }
+
+    fn body(&mut self, detail: &mut Detail, body: &Body, headers: &[(String, String)], path: &str) {
+        match body.mode.as_deref() {
+            Some("raw") => {
+                let raw = self.substitute(body.raw.as_deref().unwrap_or_default());
+
+                if raw.trim().is_empty() {
+                    return;
+                }
+
+                let language = body
+                    .options
+                    .as_ref()
+                    .and_then(|options| options.pointer("/raw/language"))
+                    .and_then(Value::as_str);
+
+                let json_header = headers.iter().any(|(name, value)| {
+                    name.eq_ignore_ascii_case("content-type") && value.contains("json")
+                });
+
+                match serde_json::from_str(&raw) {
+                    Ok(json) => detail.body = Some(json),
+                    Err(_) if language == Some("json") || json_header => self.warn(format!(
+                        "'{path}' has an invalid JSON body, which was skipped."
+                    )),
+                    Err(_) => self.warn(format!(
+                        "'{path}' has a {} body, but only JSON bodies are supported.",
+                        language.unwrap_or("text")
+                    )),
+                }
+            }
+            Some("urlencoded") => {
+                detail.form = Some(
+                    body.urlencoded
+                        .iter()
+                        .filter(|field| !field.disabled)
+                        .filter_map(|field| {
+                            Some((
+                                field.key.clone()?,
+                                self.substitute(&text(field.value.as_ref())),
+                            ))
+                        })
+                        .collect(),
+                );
+            }
+            Some("graphql") => {
+                let graphql = body.graphql.as_ref();
+                let query = graphql.and_then(|g| g.query.as_deref()).unwrap_or_default();
+
+                let variables = graphql
+                    .and_then(|g| g.variables.as_deref())
+                    .map(|variables| self.substitute(variables))
+                    .filter(|variables| !variables.trim().is_empty())
+                    .and_then(|variables| serde_json::from_str(&variables).ok())
+                    .unwrap_or(Value::Null);
+
+                detail.body = Some(serde_json::json!({
+                    "query": self.substitute(query),
+                    "variables": variables,
+                }));
+            }
+            Some(mode) => self.warn(format!(
+                "'{path}' has a {mode} body, but only JSON, urlencoded and GraphQL bodies are supported."
+            )),
+            None => {}
+        }
+    }
+
+    /// Warns about scripts, which can't be converted.
+    fn events(&mut self, events: &[Event], path: &str) {
+        for event in events {
+            let has_script = match event.script.as_ref().and_then(|s| s.get("exec")) {
+                Some(Value::Array(lines)) => lines
+                    .iter()
+                    .any(|line| line.as_str().is_some_and(|line| !line.trim().is_empty())),
+                Some(Value::String(script)) => !script.trim().is_empty(),
+                _ => false,
+            };
+
+            if has_script {
+                self.warn(format!(
+                    "'{path}' has a {} script, which is not supported.",
+                    event.listen.as_deref().unwrap_or("event")
+                ));
+            }
+        }
+    }
+
+    /// Value of an auth setting, e.g. the token of bearer auth.
+    fn setting(&mut self, auth: &Auth, key: &str) -> Option<String> {
+        let value = match auth.settings.get(&auth.kind)? {
+            Value::Array(settings) => settings
+                .iter()
+                .find(|setting| setting.get("key").and_then(Value::as_str) == Some(key))
+                .and_then(|setting| setting.get("value"))
+                .map(|value| text(Some(value))),
+            // Collections exported by older versions use an object
+            Value::Object(settings) => settings.get(key).map(|value| text(Some(value))),
+            _ => None,
+        }?;
+
+        Some(self.substitute(&value))
+    }
+
+    /// Replaces `{{variable}}` with the value of collection variables,
+    /// warning once about each variable which isn't defined.
+    fn substitute(&mut self, value: &str) -> String {
+        let mut out = String::new();
+        let mut rest = value;
+
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+
+            let name = &rest[start + 2..start + end];
+
+            out.push_str(&rest[..start]);
+
+            match self.variables.get(name.trim()) {
+                Some(value) => out.push_str(value),
+                None => {
+                    out.push_str(&rest[start..start + end + 2]);
+
+                    if self.undefined.insert(name.to_string()) {
+                        self.warn(match variables::is_name(name.trim()) {
+                            true => format!(
+                                "Variable '{{{{{name}}}}}' is not defined in the collection, so it's empty to be filled in."
+                            ),
+                            false => format!(
+                                "Variable '{{{{{name}}}}}' is not defined in the collection and was left as is."
+                            ),
+                        });
+                    }
+                }
+            }
+
+            rest = &rest[start + end + 2..];
+        }
+
+        out.push_str(rest);
+
+        out
+    }
+
+    fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+}
+
+/// Auth set on a folder or request, other than to inherit its parent's.
+fn own(auth: Option<&Auth>) -> Option<&Auth> {
+    auth.filter(|auth| auth.kind != "inherit")
+}
+
+/// URL with each `{{variable}}` swapped for a token which is left as is when
+/// the URL is parsed, along with the variables it replaced.
+fn mask(url: &str) -> (String, Vec<(String, String)>) {
+    let mut masked = String::new();
+    let mut placeholders = vec![];
+    let mut rest = url;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+
+        let token = format!("corkscrew-placeholder-{}", placeholders.len());
+
+        masked.push_str(&rest[..start]);
+        masked.push_str(&token);
+        placeholders.push((token, rest[start..start + end + 2].to_string()));
+
+        rest = &rest[start + end + 2..];
+    }
+
+    masked.push_str(rest);
+
+    (masked, placeholders)
+}
+
+/// Puts back the variables swapped for tokens by `mask`.
+fn unmask(mut detail: Detail, placeholders: &[(String, String)]) -> Detail {
+    // Later tokens first, so `-1` isn't replaced in `-10`
+    let unmask = |value: &str| {
+        placeholders
+            .iter()
+            .rev()
+            .fold(value.to_string(), |value, (token, placeholder)| {
+                value.replace(token, placeholder)
+            })
+    };
+
+    detail.host = detail.host.as_deref().map(unmask);
+    detail.resource = detail.resource.as_deref().map(unmask);
+    detail.hash = detail.hash.as_deref().map(unmask);
+    detail.params = detail.params.map(|params| {
+        params
+            .iter()
+            .map(|(name, value)| (unmask(name), unmask(value)))
+            .collect()
+    });
+
+    detail
+}
@@ -0,0 +1,138 @@
+use std::{
+    error::Error,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::task::JoinSet;
+
+use crate::{
+    builder, parser, run,
+    types::{Bench, BenchReport, Detail, Policy},
+    Executor, Settings,
+};
+
+pub async fn go(settings: Settings, bench: Bench) -> Result<Vec<BenchReport>, Box<dyn Error>> {
+    let contents = run::read_config(&settings);
+
+    let details: Vec<Detail> =
+        parser::parse_selected(&contents, &settings.selection(&bench.request_names))
+            .into_iter()
+            .map(|(_, detail)| detail)
+            .collect();
+
+    let policies = run::policies(&details, &settings);
+
+    let names: Vec<String> = details.iter().filter_map(|d| d.name.clone()).collect();
+
+    let mut requests = builder::build(details)?;
+
+    let executor = Executor::new()?;
+
+    let mut reports = vec![];
+
+    for name in names {
+        // Only the last of any requests with the same name is built.
+        let Some(request) = requests.remove(&name) else {
+            continue;
+        };
+
+        // Responses are only measured, as every iteration would save to the same file
+        let policy = Policy {
+            save: None,
+            ..policies.get(&name).cloned().unwrap_or_default()
+        };
+
+        reports.push(measure(&executor, name, request, policy, &bench).await?);
+    }
+
+    Ok(reports)
+}
+
+async fn measure(
+    executor: &Executor,
+    name: String,
+    request: reqwest::Request,
+    policy: Policy,
+    bench: &Bench,
+) -> Result<BenchReport, Box<dyn Error>> {
+    let repeat = match (bench.repeat, bench.duration) {
+        (Some(repeat), _) => repeat,
+        (None, Some(_)) => u64::MAX,
+        (None, None) => 10,
+    };
+
+    let remaining = Arc::new(AtomicU64::new(repeat));
+    let request = Arc::new(request);
+    let policy = Arc::new(policy);
+
+    let started = Instant::now();
+    let deadline = bench.duration.map(|duration| started + duration);
+
+    let mut workers = JoinSet::new();
+
+    for _ in 0..bench.concurrency.max(1) {
+        let executor = executor.clone();
+        let remaining = remaining.clone();
+        let request = request.clone();
+        let policy = policy.clone();
+
+        workers.spawn(async move {
+            let mut latencies = Vec::<Duration>::new();
+            let mut errors = 0;
+            let mut requests = 0;
+
+            while deadline.is_none_or(|deadline| Instant::now() < deadline)
+                && remaining
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |r| r.checked_sub(1))
+                    .is_ok()
+            {
+                let Some(request) = request.try_clone() else {
+                    return Err(String::from(
+                        "Requests with streaming bodies can't be repeated.",
+                    ));
+                };
+
+                requests += 1;
+
+                let execution = executor.send(request, &policy).await;
+
+                match execution.response {
+                    Some(response) => {
+                        if !response.is_success() {
+                            errors += 1;
+                        }
+
+                        latencies.push(execution.timings.total);
+                    }
+                    None => errors += 1,
+                }
+            }
+
+            Ok((latencies, errors, requests))
+        });
+    }
+
+    let mut latencies = vec![];
+    let mut errors = 0;
+    let mut requests = 0;
+
+    while let Some(worker) = workers.join_next().await {
+        let (l, e, r) = worker??;
+
+        latencies.extend(l);
+        errors += e;
+        requests += r;
+    }
+
+    Ok(BenchReport::new(
+        name,
+        latencies,
+        errors,
+        requests,
+        started.elapsed(),
+    ))
+}
@@ -3,6 +3,9 @@ use reqwest::header::HeaderValue;
 use crate::types::{AuthType, Detail, Method};
 use std::{collections::HashMap, error::Error, time::Duration};
 
+/// Seconds before a request times out, unless it sets its own timeout.
+pub const DEFAULT_TIMEOUT: u64 = 10;
+
 pub fn build(details: Vec<Detail>) -> Result<HashMap<String, reqwest::Request>, Box<dyn Error>> {
     let mut requests = HashMap::<String, reqwest::Request>::new();
 
@@ -49,7 +52,10 @@ pub fn build(details: Vec<Detail>) -> Result<HashMap<String, reqwest::Request>,
         }
 
         if let Some(h) = &request_detail.headers {
-            headers = h.try_into().expect("Expected to receive valid headers.")
+            let h: reqwest::header::HeaderMap =
+                h.try_into().expect("Expected to receive valid headers.");
+
+            headers.extend(h);
         }
 
         let body = request_detail
@@ -70,7 +76,7 @@ pub fn build(details: Vec<Detail>) -> Result<HashMap<String, reqwest::Request>,
             }
         }
 
-        let timeout = request_detail.timeout.unwrap_or(10);
+        let timeout = request_detail.timeout.unwrap_or(DEFAULT_TIMEOUT);
 
         if !form.is_empty() {
             let req = reqwest::Client::new()
@@ -1,18 +1,328 @@
-use std::{collections::HashMap, error::Error};
+use crate::{
+    limiter::RateLimiter,
+    resolver::TimedResolver,
+    types::{
+        output::size, snapshot, Execution, Policy, ResponseSnapshot, Retry, Save, Saved, Timings,
+    },
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::{fs, io::AsyncWriteExt, sync::Semaphore, task::JoinSet};
+
+/// How long a download must take before showing progress.
+const PROGRESS_AFTER: Duration = Duration::from_millis(500);
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+type SendError = Box<dyn Error + Send + Sync>;
 
 pub async fn exec(
     requests: HashMap<String, reqwest::Request>,
-) -> Result<HashMap<String, Box<reqwest::Response>>, Box<dyn Error>> {
-    let mut responses = HashMap::<String, Box<reqwest::Response>>::new();
+    policies: &HashMap<String, Policy>,
+    parallel: usize,
+) -> Result<HashMap<String, Execution>, Box<dyn Error>> {
+    let mut executions = HashMap::<String, Execution>::new();
+
+    exec_each(requests, policies, parallel, |request_name, execution| {
+        executions.insert(request_name, execution);
+    })
+    .await?;
+
+    Ok(executions)
+}
+
+/// Executes requests, passing each execution to `on_complete` as soon as it
+/// completes.
+pub async fn exec_each<F>(
+    requests: HashMap<String, reqwest::Request>,
+    policies: &HashMap<String, Policy>,
+    parallel: usize,
+    mut on_complete: F,
+) -> Result<(), Box<dyn Error>>
+where
+    F: FnMut(String, Execution),
+{
+    let executor = Executor::new()?;
+    let permits = Arc::new(Semaphore::new(parallel.max(1)));
+
+    let mut tasks = JoinSet::new();
 
     for (request_name, request) in requests {
-        match reqwest::Client::new().execute(request).await {
-            Ok(response) => {
-                responses.insert(request_name, Box::new(response));
+        let executor = executor.clone();
+        let permits = permits.clone();
+        let policy = policies.get(&request_name).cloned().unwrap_or_default();
+
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+
+            (request_name, executor.send(request, &policy).await)
+        });
+    }
+
+    while let Some(task) = tasks.join_next().await {
+        let (request_name, execution) = task?;
+
+        on_complete(request_name, execution);
+    }
+
+    Ok(())
+}
+
+/// Client shared between requests, along with the state needed to rate limit
+/// and time them.
+#[derive(Clone)]
+pub struct Executor {
+    client: reqwest::Client,
+    limiter: Arc<RateLimiter>,
+    resolver: Arc<TimedResolver>,
+}
+
+impl Executor {
+    pub fn new() -> Result<Executor, reqwest::Error> {
+        let resolver = Arc::new(TimedResolver::new());
+
+        let client = reqwest::Client::builder()
+            .dns_resolver(resolver.clone())
+            .build()?;
+
+        Ok(Executor {
+            client,
+            limiter: Arc::new(RateLimiter::new()),
+            resolver,
+        })
+    }
+
+    pub async fn send(&self, request: reqwest::Request, policy: &Policy) -> Execution {
+        let started_at = SystemTime::now();
+        let started = Instant::now();
+        let mut attempts = 0;
+
+        let result = self.attempt(request, policy, &mut attempts).await;
+
+        let duration = started.elapsed();
+
+        match result {
+            Ok((response, timings)) => Execution {
+                response: Some(response),
+                error: None,
+                attempts,
+                timings,
+                started: started_at,
+                duration,
+            },
+            Err(e) => Execution {
+                response: None,
+                error: Some(describe(&*e)),
+                attempts,
+                timings: Timings::default(),
+                started: started_at,
+                duration,
+            },
+        }
+    }
+
+    async fn attempt(
+        &self,
+        request: reqwest::Request,
+        policy: &Policy,
+        attempts: &mut u32,
+    ) -> Result<(ResponseSnapshot, Timings), SendError> {
+        let retry = policy.retry.clone().unwrap_or(Retry {
+            attempts: Some(1),
+            ..Retry::new()
+        });
+
+        let host = request.url().host_str().unwrap_or_default().to_string();
+
+        let mut request = request;
+
+        loop {
+            *attempts += 1;
+
+            // Requests with streaming bodies can't be cloned, so can only be sent once.
+            let next = match *attempts < retry.max_attempts() {
+                true => request.try_clone(),
+                false => None,
+            };
+
+            for rate in &policy.rate_limits {
+                self.limiter.acquire(&host, rate).await;
             }
-            Err(e) => eprintln!("{}", e),
+
+            let started = Instant::now();
+
+            let delay = match self.client.execute(request).await {
+                Ok(response) => {
+                    if next.is_none() || !retry.should_retry_status(response.status().as_u16()) {
+                        let timings = Timings {
+                            dns: self.resolver.take_lookup_since(&host, started),
+                            ttfb: started.elapsed(),
+                            ..Timings::default()
+                        };
+
+                        return receive(response, started, timings, policy.save.as_ref()).await;
+                    }
+
+                    match retry_after(&response).filter(|_| retry.honours_retry_after()) {
+                        Some(delay) => retry.cap(delay),
+                        None => retry.backoff(*attempts),
+                    }
+                }
+                Err(e) => {
+                    if next.is_none() || !retry.should_retry_error(&e) {
+                        return Err(e.into());
+                    }
+
+                    retry.backoff(*attempts)
+                }
+            };
+
+            tokio::time::sleep(delay).await;
+
+            request = next.expect("retries should only continue with a cloned request");
+        }
+    }
+}
+
+async fn receive(
+    response: reqwest::Response,
+    started: Instant,
+    timings: Timings,
+    save: Option<&Save>,
+) -> Result<(ResponseSnapshot, Timings), SendError> {
+    let status = response.status().as_u16();
+    let version = format!("{:?}", response.version());
+    let url = response.url().to_string();
+    let headers = snapshot::headers(response.headers());
+
+    let (body, saved) = match save {
+        Some(save) => (vec![], Some(download(response, save).await?)),
+        None => (response.bytes().await?.to_vec(), None),
+    };
+
+    let total = started.elapsed();
+
+    Ok((
+        ResponseSnapshot {
+            status,
+            version,
+            url,
+            headers,
+            body,
+            saved,
+        },
+        Timings {
+            download: total - timings.ttfb,
+            total,
+            ..timings
+        },
+    ))
+}
+
+/// Streams a response body to disk, so large bodies aren't held in memory.
+async fn download(mut response: reqwest::Response, save: &Save) -> Result<Saved, SendError> {
+    let failed = |e: std::io::Error| format!("Failed to save to {}: {}", save.path.display(), e);
+
+    if let Some(dir) = save.path.parent() {
+        fs::create_dir_all(dir).await.map_err(failed)?;
+    }
+
+    if save.headers {
+        let mut head = format!("{:?} {}\n", response.version(), response.status());
+
+        for (name, value) in snapshot::headers(response.headers()) {
+            head.push_str(&format!("{name}: {value}\n"));
+        }
+
+        fs::write(save.headers_path(), head).await.map_err(failed)?;
+    }
+
+    let mut file = fs::File::create(&save.path).await.map_err(failed)?;
+
+    let total = response.content_length();
+    let started = Instant::now();
+    let mut shown: Option<Instant> = None;
+    let mut received: u64 = 0;
+
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await.map_err(failed)?;
+        received += chunk.len() as u64;
+
+        let due = match shown {
+            Some(shown) => shown.elapsed() >= PROGRESS_INTERVAL,
+            None => started.elapsed() >= PROGRESS_AFTER,
         };
+
+        if save.progress && due {
+            progress(save, received, total);
+            shown = Some(Instant::now());
+        }
+    }
+
+    file.flush().await.map_err(failed)?;
+
+    if shown.is_some() {
+        progress(save, received, total);
+        eprintln!();
     }
 
-    Ok(responses)
+    Ok(Saved {
+        path: save.path.clone(),
+        size: received,
+    })
+}
+
+fn progress(save: &Save, received: u64, total: Option<u64>) {
+    let total = match total {
+        Some(total) if total > 0 => {
+            format!(" / {} ({}%)", size(total as usize), received * 100 / total)
+        }
+        _ => String::new(),
+    };
+
+    eprint!(
+        "\r{}: {}{}",
+        save.path.display(),
+        size(received as usize),
+        total
+    );
+}
+
+/// Describes an error along with its underlying causes, which for client
+/// errors carry the useful detail, e.g. 'Connection refused'.
+fn describe(error: &dyn Error) -> String {
+    let mut description = error.to_string();
+    let mut source = error.source();
+
+    while let Some(cause) = source {
+        let cause_description = cause.to_string();
+
+        // Some errors already include their cause in their own description.
+        if !description.contains(&cause_description) {
+            description.push_str(&format!(": {cause_description}"));
+        }
+
+        source = cause.source();
+    }
+
+    description
+}
+
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let value = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?;
+
+    match value.trim().parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => httpdate::parse_http_date(value)
+            .ok()?
+            .duration_since(SystemTime::now())
+            .ok(),
+    }
 }
@@ -0,0 +1,47 @@
+use std::error::Error;
+
+use crate::{
+    builder,
+    convert::{curl, http},
+    parser, run,
+    types::{Detail, Export, ExportFormat},
+    Settings,
+};
+
+/// Exports the resolved requests in the given format, in the order they are
+/// defined.
+pub fn go(settings: Settings, export: Export) -> Result<String, Box<dyn Error>> {
+    let contents = run::read_config(&settings);
+
+    let details: Vec<Detail> =
+        parser::parse_selected(&contents, &settings.selection(&export.request_names))
+            .into_iter()
+            .map(|(_, detail)| detail)
+            .collect();
+
+    let mut requests = builder::build(details.clone())?;
+
+    let mut out = String::new();
+
+    for detail in details {
+        let Some(name) = &detail.name else {
+            continue;
+        };
+
+        // Only the last of any requests with the same name is built.
+        let Some(request) = requests.remove(name) else {
+            continue;
+        };
+
+        match export.format {
+            ExportFormat::Curl => {
+                out.push_str(&format!("# {name}\n{}\n", curl::curl(&request, &detail)));
+            }
+            ExportFormat::Http => {
+                out.push_str(&format!("{}\n\n", http::http(&request, &detail)));
+            }
+        }
+    }
+
+    Ok(out)
+}
@@ -0,0 +1,507 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use crate::{
+    builder::DEFAULT_TIMEOUT,
+    convert::{self, curl, har, http, openapi, postman},
+    parser,
+    types::{Detail, Import, ImportFormat, Inherit, Inheritance, Retry},
+    variables,
+};
+
+/// Converts requests from another format into request definitions, as YAML
+/// or merged into an existing file if `--into` is given.
+///
+/// Requests are merged by adding their YAML to the text of the file, so its
+/// comments and formatting are kept. Nothing is written if they can't be added
+/// that way, e.g. to a group whose requests are written inline.
+pub fn go(import: Import) -> Result<Imported, Box<dyn Error>> {
+    let filtered = !import.hosts.is_empty()
+        || !import.methods.is_empty()
+        || !import.urls.is_empty()
+        || import.strip_cookies
+        || import.strip_auth;
+
+    if filtered && import.format != ImportFormat::Har {
+        return Err(
+            "--host, --method, --url, --strip-cookies and --strip-auth only apply to HAR imports."
+                .into(),
+        );
+    }
+
+    let input = match &import.path {
+        Some(path) if path != Path::new("-") => fs::read_to_string(path)?,
+        _ => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        }
+    };
+
+    let (details, warnings) = match import.format {
+        ImportFormat::Curl => (curl::import(&input)?, vec![]),
+        ImportFormat::Har => har::import(&input, &import)?,
+        ImportFormat::Http => http::import(&input)?,
+        ImportFormat::Postman => postman::import(&input)?,
+        ImportFormat::OpenApi => openapi::import(&input)?,
+    };
+
+    let count = count(&details);
+
+    if count == 0 {
+        return Err("No requests found to import.".into());
+    }
+
+    let Some(into) = &import.into else {
+        let details = convert::unique_names(details, &HashSet::new());
+
+        let details = match &import.group {
+            Some(group) => vec![group_of(group, details)],
+            None => details,
+        };
+
+        return Ok(Imported {
+            yaml: Some(serde_yaml::to_string(&details)?),
+            count,
+            warnings,
+        });
+    };
+
+    let source = match into.exists() {
+        true => fs::read_to_string(into)?,
+        false => String::new(),
+    };
+
+    let mut existing: Vec<Detail> = match source.trim().is_empty() {
+        true => vec![],
+        false => serde_yaml::from_str(&source)
+            .map_err(|e| format!("Failed to parse {}: {}", into.display(), e))?,
+    };
+
+    let details = convert::unique_names(details, &names(&existing));
+
+    let (group, added) = match &import.group {
+        Some(group) => match find_group(&mut existing, group, &Detail::new()) {
+            Some((parent, resolved)) => {
+                let added: Vec<Detail> = details
+                    .into_iter()
+                    .map(|d| fit(d, &Detail::new(), &resolved))
+                    .collect::<Result<_, _>>()?;
+
+                parent
+                    .requests
+                    .get_or_insert_with(Vec::new)
+                    .extend(added.clone());
+
+                (Some(group.as_str()), added)
+            }
+            None => (None, vec![group_of(group, details)]),
+        },
+        None => (None, details),
+    };
+
+    if group.is_none() {
+        existing.extend(added.clone());
+    }
+
+    let merged = merge_source(&source, group, &added, &existing)?.ok_or_else(|| {
+        format!(
+            "Unable to add the requests to {} without rewriting it, so it was left as is. Import without --into to print them instead.",
+            into.display()
+        )
+    })?;
+
+    fs::write(into, merged)?;
+
+    Ok(Imported {
+        yaml: None,
+        count,
+        warnings,
+    })
+}
+
+pub struct Imported {
+    /// Imported requests as YAML, unless merged into a file.
+    pub yaml: Option<String>,
+    /// Number of requests imported, not counting groups.
+    pub count: usize,
+    /// Anything which couldn't be converted.
+    pub warnings: Vec<String>,
+}
+
+fn group_of(name: &str, details: Vec<Detail>) -> Detail {
+    Detail {
+        name: Some(name.to_string()),
+        requests: Some(details),
+        ..Detail::new()
+    }
+}
+
+/// Text of `source` with requests `added` to the end of it, or to the end of
+/// the requests of the first `group` it can be added to, as long as it then
+/// defines the `merged` requests.
+fn merge_source(
+    source: &str,
+    group: Option<&str>,
+    added: &[Detail],
+    merged: &[Detail],
+) -> Result<Option<String>, Box<dyn Error>> {
+    let lines: Vec<Line> = Line::split(source);
+
+    let candidates = match group {
+        Some(group) => lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.names(group))
+            .map(|(index, _)| add_to_group(source, &lines, index, added))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect(),
+        None => {
+            // Added at the same indentation as the requests already defined
+            let indent = lines
+                .iter()
+                .find(|line| line.is_item())
+                .map_or(0, |line| line.indent);
+
+            vec![insert(source, source.len(), &yaml(added, indent)?)]
+        }
+    };
+
+    Ok(candidates.into_iter().find(|candidate| {
+        serde_yaml::from_str::<Vec<Detail>>(candidate).is_ok_and(|details| details == merged)
+    }))
+}
+
+/// Text of `source` with requests added to the group whose name is on
+/// `lines[index]`, if its requests are written as a block.
+fn add_to_group(
+    source: &str,
+    lines: &[Line],
+    index: usize,
+    added: &[Detail],
+) -> Result<Option<String>, Box<dyn Error>> {
+    let indent = lines[index].key_indent();
+
+    // Lines of the group, up to the next line indented less than its keys
+    let group: Vec<&Line> = lines[index + 1..]
+        .iter()
+        .filter(|line| line.is_content())
+        .take_while(|line| line.indent >= indent)
+        .collect();
+
+    let Some(requests) = group
+        .iter()
+        .position(|line| line.indent == indent && line.key() == Some("requests"))
+    else {
+        let end = group.last().map_or(&lines[index], |line| *line).end;
+        let added = format!(
+            "{}requests:\n{}",
+            " ".repeat(indent),
+            yaml(added, indent + 2)?
+        );
+
+        return Ok(Some(insert(source, end, &added)));
+    };
+
+    if !group[requests].value().is_empty() {
+        return Ok(None);
+    }
+
+    // Items may be at the same indentation as the key they're under
+    let items: Vec<&&Line> = group[requests + 1..]
+        .iter()
+        .take_while(|line| line.indent > indent || line.indent == indent && line.is_item())
+        .collect();
+
+    let (end, item_indent) = match (items.first(), items.last()) {
+        (Some(first), Some(last)) => (last.end, first.indent),
+        _ => (group[requests].end, indent + 2),
+    };
+
+    Ok(Some(insert(source, end, &yaml(added, item_indent)?)))
+}
+
+/// Requests as YAML, indented by `indent` spaces.
+fn yaml(details: &[Detail], indent: usize) -> Result<String, serde_yaml::Error> {
+    let yaml = serde_yaml::to_string(details)?;
+
+    Ok(yaml
+        .lines()
+        .map(|line| match line.is_empty() {
+            true => String::from("\n"),
+            false => format!("{}{line}\n", " ".repeat(indent)),
+        })
+        .collect())
+}
+
+/// Text of `source` with `text` inserted at `at`, on a line of its own.
+fn insert(source: &str, at: usize, text: &str) -> String {
+    let (before, after) = source.split_at(at);
+
+    let separator = match before.is_empty() || before.ends_with('\n') {
+        true => "",
+        false => "\n",
+    };
+
+    format!("{before}{separator}{text}{after}")
+}
+
+/// Line of a YAML file, with where it ends, including its line break.
+struct Line<'a> {
+    text: &'a str,
+    indent: usize,
+    end: usize,
+}
+
+impl<'a> Line<'a> {
+    fn split(source: &'a str) -> Vec<Line<'a>> {
+        let mut end = 0;
+
+        source
+            .split_inclusive('\n')
+            .map(|text| {
+                end += text.len();
+
+                Line {
+                    text: text.trim_end(),
+                    indent: text.len() - text.trim_start_matches(' ').len(),
+                    end,
+                }
+            })
+            .collect()
+    }
+
+    /// Whether the line is more than whitespace or a comment.
+    fn is_content(&self) -> bool {
+        let text = self.text.trim_start();
+        !text.is_empty() && !text.starts_with('#')
+    }
+
+    /// Whether the line starts an item of a sequence.
+    fn is_item(&self) -> bool {
+        let text = self.text.trim_start();
+        text == "-" || text.starts_with("- ")
+    }
+
+    /// Text of a mapping entry, without any `- ` starting a sequence item.
+    fn entry(&self) -> &'a str {
+        let text = self.text.trim_start();
+        text.strip_prefix("- ").unwrap_or(text).trim_start()
+    }
+
+    /// Indentation of the keys of the mapping the line is part of.
+    fn key_indent(&self) -> usize {
+        self.text.len() - self.entry().len()
+    }
+
+    fn key(&self) -> Option<&'a str> {
+        self.entry().split_once(':').map(|(key, _)| key.trim())
+    }
+
+    /// Value of a mapping entry, without any comment.
+    fn value(&self) -> &'a str {
+        let value = self.entry().split_once(':').map_or("", |(_, value)| value);
+        value.split(" #").next().unwrap_or_default().trim()
+    }
+
+    /// Whether the line is the name of a request called `name`.
+    fn names(&self, name: &str) -> bool {
+        self.key() == Some("name")
+            && serde_yaml::from_str::<String>(self.value()).is_ok_and(|value| value == name)
+    }
+}
+
+/// First request with the given name, searching nested requests depth first,
+/// along with its settings as resolved from its parents.
+fn find_group<'a>(
+    details: &'a mut [Detail],
+    name: &str,
+    parent: &Detail,
+) -> Option<(&'a mut Detail, Detail)> {
+    for detail in details {
+        let resolved = parser::inherit(detail, parent);
+
+        if detail.name.as_deref() == Some(name) {
+            return Some((detail, resolved));
+        }
+
+        if let Some(found) = detail
+            .requests
+            .as_mut()
+            .and_then(|requests| find_group(requests, name, &resolved))
+        {
+            return Some(found);
+        }
+    }
+
+    None
+}
+
+/// Fits a request to a group it's nested under, so it's still sent as it
+/// would be under its `original` parent, without repeating any settings it
+/// would inherit anyway. Settings of the group for how requests are run, such
+/// as tags, rate limits and where responses are saved, still apply.
+///
+/// Errors if it would inherit something it can't opt out of, such as the
+/// group's auth, fragment, content or variables.
+fn fit(mut detail: Detail, original: &Detail, parent: &Detail) -> Result<Detail, String> {
+    let resolved = parser::inherit(&detail, original);
+
+    let scheme = resolved.scheme.clone().unwrap_or(String::from("http"));
+    let port = resolved.port.unwrap_or(default_port(&scheme));
+
+    let inherited_scheme = parent.scheme.clone().unwrap_or(String::from("http"));
+    let inherited_port = parent.port.unwrap_or(default_port(&scheme));
+
+    detail.scheme = Some(scheme).filter(|scheme| *scheme != inherited_scheme);
+    detail.port = Some(port).filter(|port| *port != inherited_port);
+    detail.host = resolved
+        .host
+        .clone()
+        .filter(|host| parent.host.as_ref() != Some(host));
+    detail.auth = resolved
+        .auth
+        .clone()
+        .filter(|auth| parent.auth.as_ref() != Some(auth));
+
+    let method = resolved.method.clone().unwrap_or(String::from("get"));
+    let inherited_method = parent.method.clone().unwrap_or(String::from("get"));
+
+    detail.method = Some(method).filter(|method| *method != inherited_method);
+
+    let timeout = resolved.timeout.unwrap_or(DEFAULT_TIMEOUT);
+    let inherited_timeout = parent.timeout.unwrap_or(DEFAULT_TIMEOUT);
+
+    detail.timeout = Some(timeout).filter(|timeout| *timeout != inherited_timeout);
+
+    // A single attempt is the same as not retrying
+    let retry = resolved.retry.clone().or_else(|| {
+        parent.retry.as_ref().map(|_| Retry {
+            attempts: Some(1),
+            ..Retry::new()
+        })
+    });
+
+    detail.retry = retry.filter(|retry| parent.retry.as_ref() != Some(retry));
+    detail.hash = resolved
+        .hash
+        .clone()
+        .filter(|hash| parent.hash.as_ref() != Some(hash));
+
+    detail.content = resolved
+        .content
+        .clone()
+        .filter(|content| parent.content.as_ref() != Some(content));
+    detail.headers = resolved.headers.clone();
+    detail.params = resolved.params.clone();
+    detail.form = resolved.form.clone();
+    detail.body = resolved.body.clone();
+
+    // Anything it doesn't set, or sets differently, isn't inherited
+    let inherited = parser::inherit(&detail, parent);
+    let mut inherit = Inherit::default();
+
+    if inherited.headers != resolved.headers {
+        inherit.headers = Some(Inheritance::None);
+    }
+
+    if inherited.params != resolved.params {
+        inherit.params = Some(Inheritance::None);
+    }
+
+    if inherited.form != resolved.form {
+        inherit.form = Some(Inheritance::None);
+    }
+
+    if inherited.body != resolved.body {
+        inherit.body = Some(Inheritance::None);
+    }
+
+    if inherit != Inherit::default() {
+        detail.inherit = Some(inherit);
+    }
+
+    let fitted = parser::inherit(&detail, parent);
+
+    if resolved.resource.is_some() && sent(&fitted) != sent(&resolved) {
+        return Err(format!(
+            "'{}' would inherit settings of the group it can't leave out, such as its auth, fragment, content or variables, so it can't be imported into it.",
+            resolved.name.as_deref().unwrap_or_default()
+        ));
+    }
+
+    detail.requests = detail
+        .requests
+        .map(|requests| {
+            requests
+                .into_iter()
+                .map(|request| fit(request, &resolved, &fitted))
+                .collect::<Result<_, _>>()
+        })
+        .transpose()?;
+
+    Ok(detail)
+}
+
+/// Parts of a resolved request which affect what's sent, with any variables
+/// substituted and defaults filled in, or `None` if it can't be sent.
+fn sent(detail: &Detail) -> Option<Detail> {
+    let detail = variables::substitute(detail.clone()).ok()?;
+    let scheme = detail.scheme.unwrap_or(String::from("http"));
+
+    Some(Detail {
+        port: Some(detail.port.unwrap_or(default_port(&scheme))),
+        scheme: Some(scheme),
+        host: detail.host,
+        resource: detail.resource,
+        method: Some(detail.method.unwrap_or(String::from("get"))),
+        hash: detail.hash,
+        params: detail.params,
+        headers: detail.headers,
+        auth: detail.auth,
+        content: detail.content,
+        body: detail.body,
+        form: detail.form,
+        timeout: Some(detail.timeout.unwrap_or(DEFAULT_TIMEOUT)),
+        retry: detail.retry.filter(|retry| retry.max_attempts() > 1),
+        ..Detail::new()
+    })
+}
+
+fn default_port(scheme: &str) -> u16 {
+    match scheme {
+        "https" => 443,
+        _ => 80,
+    }
+}
+
+/// Names of all requests, including nested requests.
+fn names(details: &[Detail]) -> HashSet<String> {
+    details
+        .iter()
+        .flat_map(|detail| {
+            let nested = detail.requests.as_deref().map(names).unwrap_or_default();
+
+            detail.name.iter().cloned().chain(nested)
+        })
+        .collect()
+}
+
+/// Number of requests, not counting groups without a resource.
+fn count(details: &[Detail]) -> usize {
+    details
+        .iter()
+        .map(|detail| {
+            let nested = detail.requests.as_deref().map(count).unwrap_or_default();
+
+            usize::from(detail.resource.is_some()) + nested
+        })
+        .sum()
+}
@@ -0,0 +1,43 @@
+use crate::types::Rate;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Token bucket scheduler, holding a single token per host and rate, so that
+/// requests are spaced evenly rather than sent in bursts.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    schedule: Mutex<HashMap<(String, Rate), Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> RateLimiter {
+        RateLimiter::default()
+    }
+
+    /// Waits until a request to `host` is allowed under `rate`.
+    pub async fn acquire(&self, host: &str, rate: &Rate) {
+        let wait = self.reserve(host, rate);
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn reserve(&self, host: &str, rate: &Rate) -> Duration {
+        let now = Instant::now();
+
+        let mut schedule = self.schedule.lock().expect("rate limiter lock poisoned");
+
+        let next = schedule
+            .entry((host.to_string(), rate.clone()))
+            .or_insert(now);
+
+        let slot = (*next).max(now);
+        *next = slot + rate.interval();
+
+        slot - now
+    }
+}
@@ -1,11 +1,20 @@
+pub mod bench;
 pub mod builder;
 pub mod executor;
+pub mod export;
+pub mod import;
+pub mod limiter;
 pub mod parser;
+pub mod resolver;
 pub mod run;
 pub mod settings;
+pub mod validate;
+pub mod variables;
 
 pub use builder::*;
 pub use executor::*;
+pub use limiter::*;
 pub use parser::*;
+pub use resolver::*;
 pub use run::*;
 pub use settings::*;
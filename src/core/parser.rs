@@ -1,111 +1,451 @@
-use crate::types::Detail;
+use std::collections::{HashMap, HashSet};
 
-fn parser(source: &Vec<Detail>, target: &mut Vec<Detail>, parent_index: usize) {
+use serde_json::Value;
+
+use crate::{
+    types::{Detail, Inheritance, Selection},
+    variables,
+};
+
+fn parser(
+    source: &Vec<Detail>,
+    target: &mut Vec<Detail>,
+    paths: &mut Vec<Vec<String>>,
+    parent_index: Option<usize>,
+    parent_path: &[String],
+) {
     for request_data in source {
         if request_data.name.is_none() && request_data.requests.is_none() {
             panic!("All requests must have a name or requests.");
         }
 
-        target.push(Detail::new());
+        // Top level requests have nothing to inherit from
+        let resolved = match parent_index {
+            Some(parent_index) => inherit(request_data, &target[parent_index]),
+            None => inherit(request_data, &Detail::new()),
+        };
+
+        target.push(resolved);
         let pos = target.len() - 1;
 
-        target[pos] = Detail {
-            requests: None,
+        let mut path = parent_path.to_vec();
+        path.extend(target[pos].name.clone());
+        paths.push(path);
 
-            name: match &request_data.name {
-                Some(name) => Some(name.clone()),
-                None => target[parent_index].name.clone(),
-            },
+        // Unnamed groups don't add the name they inherit to nested requests
+        let mut path = parent_path.to_vec();
+        path.extend(request_data.name.clone());
 
-            host: match &request_data.host {
-                Some(host) => Some(host.clone()),
-                None => target[parent_index].host.clone(),
-            },
+        if let Some(requests) = &request_data.requests {
+            parser(requests, target, paths, Some(pos), &path);
+        }
+    }
+}
 
-            scheme: match &request_data.scheme {
-                Some(scheme) => Some(scheme.clone()),
-                None => target[parent_index].scheme.clone(),
-            },
+/// Resolves a request's settings, inheriting any it doesn't set from its
+/// parent.
+///
+/// Headers, params and form data are merged with those inherited, its own
+/// replacing any with the same name, less any it unsets. A JSON body replaces
+/// the inherited body. Either can be changed for each field with `inherit`.
+pub fn inherit(detail: &Detail, parent: &Detail) -> Detail {
+    let inheritance = detail.inherit.clone().unwrap_or_default();
+    let unset = detail.unset.clone().unwrap_or_default();
 
-            port: match request_data.port {
-                Some(port) => Some(port),
-                None => target[parent_index].port,
-            },
+    Detail {
+        requests: None,
 
-            timeout: match request_data.timeout {
-                Some(timeout) => Some(timeout),
-                None => target[parent_index].timeout,
-            },
+        // How a request inherits only applies to itself
+        inherit: None,
+        unset: None,
 
-            resource: match &request_data.resource {
-                Some(resource) => Some(resource.clone()),
-                None => target[parent_index].resource.clone(),
-            },
+        name: match &detail.name {
+            Some(name) => Some(name.clone()),
+            None => parent.name.clone(),
+        },
 
-            method: match &request_data.method {
-                Some(method) => Some(method.clone()),
-                None => target[parent_index].method.clone(),
-            },
+        // Variables are merged, so only those which are overridden are replaced
+        variables: match (&detail.variables, &parent.variables) {
+            (Some(variables), Some(inherited)) => {
+                let mut merged = inherited.clone();
+                merged.extend(variables.clone());
+                Some(merged)
+            }
+            (variables, inherited) => variables.clone().or(inherited.clone()),
+        },
 
-            hash: match &request_data.hash {
-                Some(hash) => Some(hash.clone()),
-                None => target[parent_index].hash.clone(),
-            },
+        // Tags are added to those inherited
+        tags: match (&detail.tags, &parent.tags) {
+            (Some(tags), Some(inherited)) => {
+                let mut merged = inherited.clone();
+                merged.extend(tags.iter().filter(|tag| !inherited.contains(tag)).cloned());
+                Some(merged)
+            }
+            (tags, inherited) => tags.clone().or(inherited.clone()),
+        },
 
-            params: match &request_data.params {
-                Some(params) => Some(params.clone()),
-                None => target[parent_index].params.clone(),
-            },
+        host: match &detail.host {
+            Some(host) => Some(host.clone()),
+            None => parent.host.clone(),
+        },
 
-            headers: match &request_data.headers {
-                Some(headers) => Some(headers.clone()),
-                None => target[parent_index].headers.clone(),
-            },
+        scheme: match &detail.scheme {
+            Some(scheme) => Some(scheme.clone()),
+            None => parent.scheme.clone(),
+        },
 
-            auth: match &request_data.auth {
-                Some(auth) => Some(auth.clone()),
-                None => target[parent_index].auth.clone(),
-            },
+        port: match detail.port {
+            Some(port) => Some(port),
+            None => parent.port,
+        },
 
-            content: match &request_data.content {
-                Some(content) => Some(content.clone()),
-                None => target[parent_index].content.clone(),
-            },
+        timeout: match detail.timeout {
+            Some(timeout) => Some(timeout),
+            None => parent.timeout,
+        },
 
-            form: match &request_data.form {
-                Some(form) => Some(form.clone()),
-                None => target[parent_index].form.clone(),
-            },
+        resource: match &detail.resource {
+            Some(resource) => Some(resource.clone()),
+            None => parent.resource.clone(),
+        },
+
+        method: match &detail.method {
+            Some(method) => Some(method.clone()),
+            None => parent.method.clone(),
+        },
 
-            body: match &request_data.body {
-                Some(body) => Some(body.clone()),
-                None => target[parent_index].body.clone(),
+        hash: match &detail.hash {
+            Some(hash) => Some(hash.clone()),
+            None => parent.hash.clone(),
+        },
+
+        params: merge(
+            &detail.params,
+            &parent.params,
+            inheritance.params.unwrap_or(Inheritance::Merge),
+            unset.params.as_deref(),
+            |a, b| a == b,
+        ),
+
+        // Header names are case insensitive
+        headers: merge(
+            &detail.headers,
+            &parent.headers,
+            inheritance.headers.unwrap_or(Inheritance::Merge),
+            unset.headers.as_deref(),
+            |a, b| a.eq_ignore_ascii_case(b),
+        ),
+
+        auth: match &detail.auth {
+            Some(auth) => Some(auth.clone()),
+            None => parent.auth.clone(),
+        },
+
+        content: match &detail.content {
+            Some(content) => Some(content.clone()),
+            None => parent.content.clone(),
+        },
+
+        form: merge(
+            &detail.form,
+            &parent.form,
+            inheritance.form.unwrap_or(Inheritance::Merge),
+            unset.form.as_deref(),
+            |a, b| a == b,
+        ),
+
+        body: match (
+            inheritance.body.unwrap_or(Inheritance::Replace),
+            &detail.body,
+        ) {
+            (Inheritance::None, body) => body.clone(),
+            (Inheritance::Merge, Some(body)) => match &parent.body {
+                Some(inherited) => Some(merge_patch(inherited.clone(), body)),
+                None => Some(body.clone()),
             },
-        };
+            (_, Some(body)) => Some(body.clone()),
+            (_, None) => parent.body.clone(),
+        },
 
-        if let Some(requests) = &request_data.requests {
-            parser(requests, target, pos);
+        retry: match &detail.retry {
+            Some(retry) => Some(retry.clone()),
+            None => parent.retry.clone(),
+        },
+
+        rate_limit: match &detail.rate_limit {
+            Some(rate_limit) => Some(rate_limit.clone()),
+            None => parent.rate_limit.clone(),
+        },
+
+        output: match &detail.output {
+            Some(output) => Some(output.clone()),
+            None => parent.output.clone(),
+        },
+
+        save_to: match &detail.save_to {
+            Some(save_to) => Some(save_to.clone()),
+            None => parent.save_to.clone(),
+        },
+
+        save_headers: match detail.save_headers {
+            Some(save_headers) => Some(save_headers),
+            None => parent.save_headers,
+        },
+    }
+}
+
+/// Map a request sets, resolved with the map it inherits, where `same`
+/// compares keys.
+fn merge(
+    own: &Option<HashMap<String, String>>,
+    inherited: &Option<HashMap<String, String>>,
+    inheritance: Inheritance,
+    unset: Option<&[String]>,
+    same: fn(&str, &str) -> bool,
+) -> Option<HashMap<String, String>> {
+    let mut inherited = match (inheritance, own) {
+        (Inheritance::None, _) | (Inheritance::Replace, Some(_)) => return own.clone(),
+        _ => inherited.clone(),
+    };
+
+    if let (Some(inherited), Some(unset)) = (&mut inherited, unset) {
+        inherited.retain(|key, _| !unset.iter().any(|name| same(name, key)));
+    }
+
+    match (own, inherited) {
+        (Some(own), Some(mut merged)) => {
+            merged.retain(|key, _| !own.keys().any(|name| same(name, key)));
+            merged.extend(own.clone());
+            Some(merged)
+        }
+        (own, inherited) => own.clone().or(inherited),
+    }
+}
+
+/// Applies a JSON merge patch (RFC 7396), where objects are merged, `null`
+/// removes a key and anything else replaces the target.
+fn merge_patch(target: Value, patch: &Value) -> Value {
+    let Value::Object(patch) = patch else {
+        return patch.clone();
+    };
+
+    let mut merged = match target {
+        Value::Object(target) => target,
+        _ => serde_json::Map::new(),
+    };
+
+    for (key, value) in patch {
+        match value {
+            Value::Null => {
+                merged.remove(key);
+            }
+            value => {
+                let target = merged.remove(key).unwrap_or(Value::Null);
+                merged.insert(key.clone(), merge_patch(target, value));
+            }
         }
     }
+
+    Value::Object(merged)
 }
 
 pub fn parse(source: &str, request_names: Vec<String>) -> Vec<Detail> {
-    let s = &serde_yaml::from_str(source).expect("Failed to parse config from provided yaml.");
+    parse_selected(source, &Selection::names(request_names))
+        .into_iter()
+        .map(|(_, detail)| detail)
+        .collect()
+}
+
+/// Error for requests with the same name, which only run if duplicates are
+/// allowed.
+pub(crate) fn duplicate(name: &str) -> String {
+    format!(
+        "More than one request is named '{name}', so only the last would run. \
+        Rename them, or use --allow-duplicates to run only the last."
+    )
+}
+
+/// Parses the selected requests along with their path in the tree.
+///
+/// Requests which share a name with requests in other groups are named by
+/// their path instead, e.g. `api.users.get_user`, so each can be run. Any
+/// which still have the same name, such as in the same group, are an error
+/// unless duplicates are allowed.
+pub fn parse_selected(source: &str, selection: &Selection) -> Vec<(Vec<String>, Detail)> {
+    let s = &deserialize(source).unwrap_or_else(|e| panic!("Failed to parse config: {e}"));
 
     let mut request_config = vec![];
+    let mut paths = vec![];
 
-    parser(s, &mut request_config, 0);
+    parser(s, &mut request_config, &mut paths, None, &[]);
 
-    request_config
+    let mut requests: Vec<(Vec<String>, Detail)> = paths
         .into_iter()
-        .filter(|r| {
-            let has_resource = r.resource.is_some();
-            let has_name = r.name.is_some();
-            let run_all = request_names.is_empty();
-
-            return has_resource
-                && has_name
-                && (run_all || request_names.contains(r.name.as_ref().unwrap()));
+        .zip(request_config)
+        .filter(|(_, r)| r.resource.is_some() && r.name.is_some())
+        .collect();
+
+    qualify(&mut requests);
+
+    if !selection.allow_duplicates {
+        if let Some(name) = duplicates(&requests).first() {
+            panic!("{}", duplicate(name));
+        }
+    }
+
+    requests
+        .into_iter()
+        .filter(|(path, r)| {
+            selection.selects(path, r.name.as_deref().unwrap_or_default())
+                && selection.tagged(r.tags.as_deref().unwrap_or_default())
+        })
+        .map(|(path, r)| {
+            (
+                path,
+                variables::substitute(r).unwrap_or_else(|e| panic!("{e}")),
+            )
         })
         .collect()
 }
+
+/// Names requests which share a name with requests in other groups by their
+/// path, e.g. `api.users.get_user`.
+pub fn qualify(requests: &mut [(Vec<String>, Detail)]) {
+    let mut paths: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for (path, detail) in requests.iter() {
+        if let Some(name) = &detail.name {
+            paths
+                .entry(name.clone())
+                .or_default()
+                .insert(path.join("."));
+        }
+    }
+
+    for (path, detail) in requests.iter_mut() {
+        let shared = detail
+            .name
+            .as_ref()
+            .and_then(|name| paths.get(name))
+            .is_some_and(|paths| paths.len() > 1);
+
+        if shared {
+            detail.name = Some(path.join("."));
+        }
+    }
+}
+
+/// Names used by more than one request, in the order they're first used.
+pub fn duplicates(requests: &[(Vec<String>, Detail)]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut duplicates = vec![];
+
+    for name in requests
+        .iter()
+        .filter_map(|(_, detail)| detail.name.as_ref())
+    {
+        if !seen.insert(name) && !duplicates.contains(name) {
+            duplicates.push(name.clone());
+        }
+    }
+
+    duplicates
+}
+
+/// Deserializes request definitions, describing any error by where it is in
+/// the file, e.g. `14:5: unknown field 'parms' (did you mean 'params'?)`.
+pub fn deserialize(source: &str) -> Result<Vec<Detail>, String> {
+    serde_yaml::from_str(source).map_err(|e| describe(&e))
+}
+
+fn describe(error: &serde_yaml::Error) -> String {
+    let mut message = error.to_string();
+
+    // Where the error is, which is replaced by the line and column
+    if let Some(location) = error.location() {
+        let suffix = format!(" at line {} column {}", location.line(), location.column());
+
+        if let Some(stripped) = message.strip_suffix(&suffix) {
+            message = stripped.to_string();
+        }
+    }
+
+    if message.starts_with('.') {
+        if let Some((_, rest)) = message.split_once(": ") {
+            message = rest.to_string();
+        }
+    }
+
+    let message = match unknown(&message) {
+        Some((kind, name, Some(suggestion))) => {
+            format!("unknown {kind} '{name}' (did you mean '{suggestion}'?)")
+        }
+        _ => message.replace('`', "'"),
+    };
+
+    match error.location() {
+        Some(location) => format!("{}:{}: {}", location.line(), location.column(), message),
+        None => message,
+    }
+}
+
+/// Kind and name of an unknown field or variant, and the closest expected name.
+fn unknown(message: &str) -> Option<(&str, &str, Option<&str>)> {
+    let rest = message.strip_prefix("unknown ")?;
+    let (kind, rest) = rest.split_once(' ')?;
+
+    if kind != "field" && kind != "variant" {
+        return None;
+    }
+
+    // Names are quoted in backticks, the unknown one first
+    let mut names = rest.split('`').skip(1).step_by(2);
+    let name = names.next()?;
+
+    Some((kind, name, suggest(name, names)))
+}
+
+/// Closest of `candidates` to a misspelled `name`, if any are close enough.
+pub fn suggest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = name.chars().count().div_ceil(3);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Edits to change one string into another, counting swapping two adjacent
+/// characters as one edit.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    distances[0] = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            let mut d = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d = d.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = d;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
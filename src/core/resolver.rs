@@ -0,0 +1,69 @@
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Lookups kept for each host, waiting to be taken by the request they were for.
+const MAX_LOOKUPS: usize = 64;
+
+/// When each lookup of a host started, and how long it took.
+type Lookups = HashMap<String, Vec<(Instant, Duration)>>;
+
+/// DNS resolver that records how long each lookup took, so it can be reported
+/// as part of a request's timings.
+///
+/// Lookups aren't made for a particular request, so they're matched to
+/// requests by host and time. Each lookup is only reported for one request,
+/// but with parallel requests to the same host, that may not be the request
+/// it was made for, so the timings are approximate.
+#[derive(Debug, Default)]
+pub struct TimedResolver {
+    lookups: Arc<Mutex<Lookups>>,
+}
+
+impl TimedResolver {
+    pub fn new() -> TimedResolver {
+        TimedResolver::default()
+    }
+
+    /// Takes the duration of the first lookup of `host` which started after
+    /// `since`, so it isn't reported for any other request.
+    pub fn take_lookup_since(&self, host: &str, since: Instant) -> Option<Duration> {
+        let mut lookups = self.lookups.lock().expect("resolver lock poisoned");
+        let lookups = lookups.get_mut(host)?;
+
+        let index = lookups.iter().position(|(started, _)| *started >= since)?;
+
+        Some(lookups.remove(index).1)
+    }
+}
+
+impl Resolve for TimedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let lookups = self.lookups.clone();
+
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let started = Instant::now();
+
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+
+            let mut lookups = lookups.lock().expect("resolver lock poisoned");
+            let lookups = lookups.entry(host).or_default();
+
+            // Lookups for requests which failed are never taken
+            if lookups.len() == MAX_LOOKUPS {
+                lookups.remove(0);
+            }
+
+            lookups.push((started, started.elapsed()));
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
@@ -1,26 +1,190 @@
-use std::{collections::HashMap, error::Error, fs};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+};
 
-use crate::{builder, executor, parser, Settings};
+use crate::{
+    builder,
+    convert::{self, http},
+    executor, parser,
+    types::{Detail, Policy, RequestOutcome, RequestSnapshot, RunReport, Save},
+    Settings,
+};
 
-pub async fn go(
-    settings: Settings,
-) -> Result<HashMap<String, Box<reqwest::Response>>, Box<dyn Error>> {
+pub async fn go(settings: Settings) -> Result<RunReport, Box<dyn Error>> {
+    go_with(settings, |_| {}).await
+}
+
+/// Runs requests, passing each outcome to `on_outcome` as soon as its request
+/// completes, in addition to returning them all in the report.
+pub async fn go_with<F>(settings: Settings, mut on_outcome: F) -> Result<RunReport, Box<dyn Error>>
+where
+    F: FnMut(&RequestOutcome),
+{
     // 2. Read in config file
-    let contents = fs::read_to_string(&settings.config_path).unwrap_or_else(|_| {
+    let contents = read_config(&settings);
+
+    // 3. Parse config
+    let (paths, details): (Vec<_>, Vec<_>) =
+        parser::parse_selected(&contents, &settings.selection(&settings.request_names))
+            .into_iter()
+            .unzip();
+
+    let policies = policies(&details, &settings);
+
+    // 4. Build requests
+    let requests = builder::build(details.clone())?;
+
+    let order: Vec<String> = details.iter().filter_map(|d| d.name.clone()).collect();
+
+    let mut details: HashMap<String, (Vec<String>, Detail)> = paths
+        .into_iter()
+        .zip(details)
+        .filter_map(|(path, d)| Some((d.name.clone()?, (path, d))))
+        .collect();
+
+    let mut snapshots: HashMap<String, RequestSnapshot> = requests
+        .iter()
+        .map(|(name, request)| (name.clone(), RequestSnapshot::from(request)))
+        .collect();
+
+    let mut outcomes = HashMap::<String, RequestOutcome>::new();
+
+    // 5. Execute requests
+    executor::exec_each(requests, &policies, settings.parallel, |name, execution| {
+        let (Some((path, detail)), Some(request)) =
+            (details.remove(&name), snapshots.remove(&name))
+        else {
+            return;
+        };
+
+        let outcome = RequestOutcome {
+            name: name.clone(),
+            path,
+            detail,
+            request,
+            response: execution.response,
+            error: execution.error,
+            attempts: execution.attempts,
+            timings: execution.timings,
+            started: execution.started,
+            duration: execution.duration,
+        };
+
+        on_outcome(&outcome);
+
+        outcomes.insert(name, outcome);
+    })
+    .await?;
+
+    // 6. Report outcomes in the order requests are defined
+    let outcomes = order
+        .iter()
+        .filter_map(|name| outcomes.remove(name))
+        .collect();
+
+    Ok(RunReport { outcomes })
+}
+
+/// Reads the config file as YAML, panicking with where in the file any
+/// requests can't be parsed, e.g. `requests.yml:14:5: unknown field 'parms'`.
+pub fn read_config(settings: &Settings) -> String {
+    let contents = read_yaml(settings);
+
+    if let Err(e) = parser::deserialize(&contents) {
+        let path = settings.config_path.display();
+
+        // Lines and columns are of the converted requests, not the file
+        match is_converted(&settings.config_path) {
+            true => panic!(
+                "{path}: invalid requests converted from file: {}",
+                message(&e)
+            ),
+            false => panic!("{path}:{e}"),
+        }
+    }
+
+    contents
+}
+
+/// Whether the config file is converted to YAML when read.
+fn is_converted(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("http" | "rest")
+    )
+}
+
+/// Message of an error from parsing requests, without its line and column.
+fn message(error: &str) -> &str {
+    match error.split_once(": ") {
+        Some((location, message)) if location.split(':').all(|n| n.parse::<usize>().is_ok()) => {
+            message
+        }
+        _ => error,
+    }
+}
+
+/// Reads the config file as YAML, converting `.http` and `.rest` files used by
+/// editor HTTP clients, without checking the requests it defines.
+pub fn read_yaml(settings: &Settings) -> String {
+    let path = &settings.config_path;
+
+    let contents = fs::read_to_string(path).unwrap_or_else(|_| {
         panic!(
             "Failed to read configuration file from {}",
             &settings.config_path.display(),
         )
     });
 
-    // 3. Parse config
-    let details = parser::parse(&contents, settings.request_names);
+    match is_converted(path) {
+        true => {
+            let (details, warnings) = http::import(&contents)
+                .unwrap_or_else(|e| panic!("Failed to parse {}: {}", path.display(), e));
 
-    // 4. Build requests
-    let requests = builder::build(details)?;
+            for warning in warnings {
+                eprintln!("warning: {warning}");
+            }
 
-    // 5. Execute requests
-    let results = executor::exec(requests).await?;
+            let details = convert::unique_names(details, &HashSet::new());
+
+            serde_yaml::to_string(&details).expect("Failed to convert requests to YAML.")
+        }
+        false => contents,
+    }
+}
+
+/// Execution policy for each named request, including any global rate limit
+/// and where to save responses.
+pub fn policies(details: &[Detail], settings: &Settings) -> HashMap<String, Policy> {
+    details
+        .iter()
+        .filter_map(|d| {
+            let mut policy = Policy::from(d);
+            policy.rate_limits.extend(settings.rate.clone());
+
+            // Paths to save to are relative to the output directory, if any
+            if let (Some(dir), Some(name)) = (&settings.output_dir, &d.name) {
+                let save = policy
+                    .save
+                    .take()
+                    .unwrap_or_else(|| Save::from_template("{{name}}", name));
+
+                policy.save = Some(Save {
+                    path: dir.join(save.path),
+                    ..save
+                });
+            }
+
+            if let Some(save) = policy.save.as_mut() {
+                save.headers |= settings.save_headers;
+                save.progress = io::stderr().is_terminal();
+            }
 
-    Ok(results)
+            Some((d.name.clone()?, policy))
+        })
+        .collect()
 }
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::cli::Cli;
+use crate::types::{
+    cli::{Cli, Command, OutputFormat, Report},
+    Extract, Rate, Selection,
+};
 use std::{error::Error, path::PathBuf};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -8,6 +11,25 @@ pub struct Settings {
     pub config_path: PathBuf,
     pub parallel: usize,
     pub request_names: Vec<String>,
+    pub rate: Option<Rate>,
+    pub command: Option<Command>,
+    pub no_color: bool,
+    pub output: OutputFormat,
+    pub reports: Vec<Report>,
+    pub verbose: u8,
+    pub show_secrets: bool,
+    /// Values to extract from every response, overriding any per request.
+    pub extract: Option<Extract>,
+    pub output_dir: Option<PathBuf>,
+    pub save_headers: bool,
+    /// Path to write requests and responses to as an HTTP Archive.
+    pub har: Option<PathBuf>,
+    /// Run only the last of any requests with the same name, rather than erroring.
+    pub allow_duplicates: bool,
+    /// Only run requests with any of these tags, or all if empty.
+    pub tags: Vec<String>,
+    /// Don't run requests with any of these tags.
+    pub exclude_tags: Vec<String>,
 }
 
 impl TryFrom<Cli> for Settings {
@@ -17,11 +39,56 @@ impl TryFrom<Cli> for Settings {
         let parallel = value.parallel.unwrap_or(0);
         let config_path = value.config_path.unwrap_or(PathBuf::from("requests.yml"));
         let request_names = value.request_names;
+        let rate = value.rate;
+        let command = value.command;
+        let no_color = value.no_color;
+        let output = value.output.unwrap_or_default();
+        let reports = value.reports;
+        let verbose = value.verbose;
+        let show_secrets = value.show_secrets;
+
+        let output_dir = value.output_dir;
+        let save_headers = value.save_headers;
+        let har = value.har;
+        let allow_duplicates = value.allow_duplicates;
+        let tags = value.tags;
+        let exclude_tags = value.exclude_tags;
+
+        let extract = match (value.select, value.header_select) {
+            (None, None) => None,
+            (select, header) => Some(Extract { select, header }),
+        };
 
         Ok(Settings {
             config_path,
             parallel,
             request_names,
+            rate,
+            command,
+            no_color,
+            output,
+            reports,
+            verbose,
+            show_secrets,
+            extract,
+            output_dir,
+            save_headers,
+            har,
+            allow_duplicates,
+            tags,
+            exclude_tags,
         })
     }
 }
+
+impl Settings {
+    /// Requests with the given names, or all if none are given.
+    pub fn selection(&self, request_names: &[String]) -> Selection {
+        Selection {
+            names: request_names.to_vec(),
+            tags: self.tags.clone(),
+            exclude_tags: self.exclude_tags.clone(),
+            allow_duplicates: self.allow_duplicates,
+        }
+    }
+}
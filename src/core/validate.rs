@@ -0,0 +1,321 @@
+use std::collections::HashSet;
+
+use serde::{de::Visitor, Deserialize};
+use serde_yaml::Value;
+
+use crate::{
+    parser, run,
+    types::{Detail, Problem},
+    variables, Settings,
+};
+
+const METHODS: [&str; 9] = [
+    "get", "post", "put", "patch", "delete", "head", "options", "trace", "connect",
+];
+
+/// Checks the requests in the config file for problems, without running them.
+pub fn go(settings: &Settings) -> Vec<Problem> {
+    validate(&run::read_yaml(settings))
+}
+
+/// Every problem with the requests defined in `source`, in the order they're
+/// defined, resolving each request as it would be run.
+pub fn validate(source: &str) -> Vec<Problem> {
+    let mut value: Value = match serde_yaml::from_str(source) {
+        Ok(value) => value,
+        Err(e) => return vec![Problem::error("", format!("Invalid YAML: {e}"))],
+    };
+
+    let mut problems = vec![];
+
+    // Unknown keys are removed, so the rest of each request can be checked
+    unknown_keys(&mut value, "", &mut problems);
+
+    let yaml = match problems.is_empty() {
+        true => source.to_string(),
+        false => serde_yaml::to_string(&value).unwrap_or_default(),
+    };
+
+    let details = match parser::deserialize(&yaml) {
+        Ok(details) => details,
+        Err(_) => {
+            // The first error in the original file has the right location, but
+            // may be a key that's already been reported
+            let error = parser::deserialize(source).err().unwrap_or_default();
+
+            let reported = problems.iter().any(|problem| {
+                let key = problem.message.split('\'').nth(1).unwrap_or_default();
+                error.contains(&format!("unknown field '{key}'"))
+            });
+
+            if !reported {
+                problems.push(Problem::error("", format!("Invalid requests: {error}")));
+            }
+
+            return problems;
+        }
+    };
+
+    if details.is_empty() {
+        problems.push(Problem::warning(
+            "",
+            String::from("No requests are defined."),
+        ));
+    }
+
+    let mut validator = Validator {
+        problems,
+        requests: vec![],
+    };
+
+    validator.requests(&details, &Detail::new(), "", &[]);
+    validator.duplicates();
+
+    validator.problems
+}
+
+struct Validator {
+    problems: Vec<Problem>,
+    /// Requests which will run, with the names of the groups they're nested
+    /// in, as resolved, and their path in the tree.
+    requests: Vec<(Vec<String>, Detail, String)>,
+}
+
+impl Validator {
+    /// Checks each request, returning whether any of them will run.
+    fn requests(
+        &mut self,
+        details: &[Detail],
+        parent: &Detail,
+        parent_path: &str,
+        parent_names: &[String],
+    ) -> bool {
+        let mut runs = false;
+
+        for (index, detail) in details.iter().enumerate() {
+            let path = path(parent_path, detail.name.as_deref(), index);
+
+            if detail.name.is_none() && detail.requests.is_none() {
+                self.problems.push(Problem::error(
+                    &path,
+                    String::from("Requests must have a name or requests."),
+                ));
+                continue;
+            }
+
+            let resolved = parser::inherit(detail, parent);
+
+            let runnable = resolved.resource.is_some() && resolved.name.is_some();
+
+            if runnable {
+                let mut names = parent_names.to_vec();
+                names.extend(resolved.name.clone());
+
+                self.request(&resolved, &path);
+                self.requests.push((names, resolved.clone(), path.clone()));
+            }
+
+            // Unnamed groups don't add the name they inherit to nested requests
+            let mut names = parent_names.to_vec();
+            names.extend(detail.name.clone());
+
+            let nested_runs = match &detail.requests {
+                Some(requests) => self.requests(requests, &resolved, &path, &names),
+                None => false,
+            };
+
+            match (&detail.requests, runnable || nested_runs) {
+                (_, true) => runs = true,
+                (Some(_), false) => self.problems.push(Problem::warning(
+                    &path,
+                    String::from("No requests in this group have a resource, so none will run."),
+                )),
+                (None, false) => self.problems.push(Problem::warning(
+                    &path,
+                    String::from("No resource is set, so this request will never run."),
+                )),
+            }
+        }
+
+        runs
+    }
+
+    /// Checks a request as resolved from its parents.
+    fn request(&mut self, resolved: &Detail, path: &str) {
+        let detail = match variables::substitute(resolved.clone()) {
+            Ok(detail) => detail,
+            Err(e) => {
+                self.problems.push(Problem::error(path, e));
+                resolved.clone()
+            }
+        };
+
+        if detail.host.as_deref().unwrap_or_default().is_empty() {
+            self.problems
+                .push(Problem::error(path, String::from("Host is required.")));
+        }
+
+        if let Some(scheme) = &detail.scheme {
+            if !matches!(scheme.as_str(), "http" | "https") {
+                self.problems.push(Problem::error(
+                    path,
+                    format!("Unknown scheme '{scheme}', expected http or https."),
+                ));
+            }
+        }
+
+        if let Some(method) = &detail.method {
+            // Any other method is sent as a GET
+            if !METHODS.contains(&method.as_str()) {
+                let message = match METHODS.contains(&method.to_lowercase().as_str()) {
+                    true => format!(
+                        "Methods are lowercase, use '{}' rather than '{method}'.",
+                        method.to_lowercase()
+                    ),
+                    false => format!(
+                        "Unknown method '{method}', expected one of {}.",
+                        METHODS.join(", ")
+                    ),
+                };
+
+                self.problems.push(Problem::error(path, message));
+            }
+        }
+
+        if detail.body.is_some() && detail.form.is_some() {
+            self.problems.push(Problem::error(
+                path,
+                String::from("Both body and form are set, but only one can be sent."),
+            ));
+        }
+
+        let mut headers: Vec<(&String, &String)> = detail.headers.iter().flatten().collect();
+        headers.sort();
+
+        for (name, value) in headers {
+            if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                self.problems.push(Problem::error(
+                    path,
+                    format!("Invalid header name '{name}'."),
+                ));
+            } else if reqwest::header::HeaderValue::from_str(value).is_err() {
+                self.problems.push(Problem::error(
+                    path,
+                    format!("Invalid value for header '{name}'."),
+                ));
+            }
+        }
+    }
+
+    /// Reports requests with the same name, after those in different groups
+    /// are named by their path.
+    fn duplicates(&mut self) {
+        let mut named: Vec<(Vec<String>, Detail)> = self
+            .requests
+            .iter()
+            .map(|(names, detail, _)| (names.clone(), detail.clone()))
+            .collect();
+
+        parser::qualify(&mut named);
+
+        let mut used = HashSet::new();
+
+        for ((_, detail), (_, _, path)) in named.iter().zip(&self.requests) {
+            let name = detail.name.clone().unwrap_or_default();
+
+            if !used.insert(name.clone()) {
+                self.problems
+                    .push(Problem::error(path, parser::duplicate(&name)));
+            }
+        }
+    }
+}
+
+/// Removes and reports keys which aren't part of a request definition, such
+/// as misspellings.
+fn unknown_keys(value: &mut Value, parent_path: &str, problems: &mut Vec<Problem>) {
+    let Value::Sequence(requests) = value else {
+        return;
+    };
+
+    for (index, request) in requests.iter_mut().enumerate() {
+        let Value::Mapping(request) = request else {
+            continue;
+        };
+
+        let name = request.get("name").and_then(Value::as_str);
+        let path = path(parent_path, name, index);
+
+        request.retain(|key, _| {
+            let key = match key {
+                Value::String(key) => key.clone(),
+                key => serde_yaml::to_string(key)
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+            };
+
+            if fields().contains(&key.as_str()) {
+                return true;
+            }
+
+            let message = match parser::suggest(&key, fields().iter().copied()) {
+                Some(field) => format!("Unknown key '{key}', did you mean '{field}'?"),
+                None => format!("Unknown key '{key}'."),
+            };
+
+            problems.push(Problem::error(&path, message));
+
+            false
+        });
+
+        if let Some(requests) = request.get_mut("requests") {
+            unknown_keys(requests, &path, problems);
+        }
+    }
+}
+
+/// Keys a request definition may have, as the fields serde expects.
+fn fields() -> &'static [&'static str] {
+    let mut fields = Fields(&[]);
+    let _ = Detail::deserialize(&mut fields);
+
+    fields.0
+}
+
+/// Deserializer which only records the fields of the struct it's given.
+struct Fields(&'static [&'static str]);
+
+impl<'de> serde::Deserializer<'de> for &mut Fields {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+        Err(serde::de::Error::custom("expected a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0 = fields;
+
+        Err(serde::de::Error::custom("only the fields are recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes
+        byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct map enum
+        identifier ignored_any
+    }
+}
+
+/// Path of a request in the tree, by name or otherwise its index.
+fn path(parent_path: &str, name: Option<&str>, index: usize) -> String {
+    match (parent_path, name) {
+        ("", Some(name)) => name.to_string(),
+        (parent_path, Some(name)) => format!("{parent_path}.{name}"),
+        (parent_path, None) => format!("{parent_path}[{index}]"),
+    }
+}
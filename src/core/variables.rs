@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::types::{AuthType, Detail};
+
+/// Replaces `{{variable}}` in a resolved request with the value of the
+/// variables it defines or inherits, erroring on any which aren't defined.
+/// Requests without any variables are left as they are.
+///
+/// Variables are replaced in the host, resource, hash, params, headers, auth,
+/// form data and string values of JSON bodies, where `\{{` is kept as `{{`.
+/// Variables may also use other variables, e.g. `base_url: https://{{host}}/api`.
+pub fn substitute(mut detail: Detail) -> Result<Detail, String> {
+    let Some(variables) = detail.variables.clone() else {
+        return Ok(detail);
+    };
+
+    let variables = resolve(variables);
+    let name = detail.name.clone().unwrap_or_default();
+
+    let replace = |value: &mut String| -> Result<(), String> {
+        *value = render(value, &variables)
+            .map_err(|variable| format!("Variable '{variable}' is not defined for '{name}'."))?;
+
+        Ok(())
+    };
+
+    for value in [&mut detail.host, &mut detail.resource, &mut detail.hash]
+        .into_iter()
+        .flatten()
+    {
+        replace(value)?;
+    }
+
+    for map in [&mut detail.params, &mut detail.headers, &mut detail.form]
+        .into_iter()
+        .flatten()
+    {
+        for value in map.values_mut() {
+            replace(value)?;
+        }
+    }
+
+    match &mut detail.auth {
+        Some(AuthType::Bearer { token }) => replace(token)?,
+        Some(AuthType::Basic { username, password }) => {
+            replace(username)?;
+            replace(password)?;
+        }
+        None => {}
+    }
+
+    if let Some(body) = &mut detail.body {
+        strings(body, &replace)?;
+    }
+
+    Ok(detail)
+}
+
+/// Renders a template, or the name of the first variable which isn't defined.
+pub fn render(template: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    expand(template, variables, true)
+}
+
+/// Renders a template, with `\{{` as `{{` if `unescape` is set or otherwise
+/// left as is.
+fn expand(
+    template: &str,
+    variables: &HashMap<String, String>,
+    unescape: bool,
+) -> Result<String, String> {
+    let mut rendered = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        if let Some(before) = rest[..start].strip_suffix('\\') {
+            match unescape {
+                true => rendered.push_str(before),
+                false => rendered.push_str(&rest[..start]),
+            }
+
+            rendered.push_str("{{");
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+
+        let name = rest[start + 2..start + end].trim();
+
+        rendered.push_str(&rest[..start]);
+
+        // Anything that doesn't look like a variable name is left as is
+        match variables.get(name) {
+            Some(value) => rendered.push_str(value),
+            None if is_name(name) => return Err(name.to_string()),
+            None => rendered.push_str(&rest[start..start + end + 2]),
+        }
+
+        rest = &rest[start + end + 2..];
+    }
+
+    rendered.push_str(rest);
+
+    Ok(rendered)
+}
+
+/// Replaces variables used in the values of other variables, as many levels
+/// deep as any reasonable set of variables would go.
+fn resolve(mut variables: HashMap<String, String>) -> HashMap<String, String> {
+    for _ in 0..8 {
+        // Escapes are kept until every variable is resolved
+        let resolved: HashMap<String, String> = variables
+            .iter()
+            .map(|(name, value)| {
+                let value = expand(value, &variables, false).unwrap_or(value.clone());
+                (name.clone(), value)
+            })
+            .collect();
+
+        if resolved == variables {
+            break;
+        }
+
+        variables = resolved;
+    }
+
+    variables
+        .into_iter()
+        .map(|(name, value)| (name, value.replace("\\{{", "{{")))
+        .collect()
+}
+
+/// Whether text between `{{` and `}}` is a variable, rather than left as is.
+pub(crate) fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+fn strings<F>(value: &mut Value, replace: &F) -> Result<(), String>
+where
+    F: Fn(&mut String) -> Result<(), String>,
+{
+    match value {
+        Value::String(value) => replace(value),
+        Value::Array(values) => values.iter_mut().try_for_each(|v| strings(v, replace)),
+        Value::Object(values) => values.values_mut().try_for_each(|v| strings(v, replace)),
+        _ => Ok(()),
+    }
+}
@@ -1,4 +1,6 @@
+pub mod convert;
 pub mod core;
+pub mod render;
 pub mod types;
 
 pub use core::*;
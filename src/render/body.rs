@@ -0,0 +1,364 @@
+use std::fmt::Write;
+
+use encoding_rs::{Encoding, UTF_8};
+
+use crate::types::output::size;
+
+/// Elements which have no closing tag in HTML.
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements whose content is shown as is rather than indented.
+const RAW_ELEMENTS: [&str; 4] = ["script", "style", "pre", "textarea"];
+
+/// Number of bytes shown in the hex summary of a binary body.
+const HEX_BYTES: usize = 64;
+
+const BLUE: &str = "\x1b[34m";
+const CYAN: &str = "\x1b[36m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Json,
+    Xml,
+    Html,
+    Text,
+    Binary,
+}
+
+/// Formats a body for display based on its `Content-Type` header.
+///
+/// JSON, XML and HTML are pretty-printed and, if `color` is set, highlighted.
+/// Text in other charsets is decoded, and binary content is summarised by its
+/// size and a hex dump of the first bytes.
+pub fn body(headers: &[(String, String)], body: &[u8], color: bool) -> String {
+    if body.is_empty() {
+        return String::new();
+    }
+
+    let content_type = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| value.as_str())
+        .unwrap_or_default();
+
+    let (mime, charset) = parse_content_type(content_type);
+
+    let text = match kind(&mime) {
+        Kind::Binary => None,
+        _ => decode(body, charset.as_deref()),
+    };
+
+    let Some(text) = text else {
+        return binary(&mime, body);
+    };
+
+    match kind(&mime) {
+        Kind::Json => json(&text, color),
+        Kind::Xml => markup(&text, false, color),
+        Kind::Html => markup(&text, true, color),
+        Kind::Text | Kind::Binary => text,
+    }
+}
+
+fn parse_content_type(value: &str) -> (String, Option<String>) {
+    let mut parts = value.split(';');
+
+    let mime = parts.next().unwrap_or_default().trim().to_lowercase();
+
+    let charset = parts.find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"').to_string())
+    });
+
+    (mime, charset)
+}
+
+fn kind(mime: &str) -> Kind {
+    let (kind, subtype) = mime.split_once('/').unwrap_or((mime, ""));
+
+    match (kind, subtype) {
+        (_, "json") => Kind::Json,
+        (_, s) if s.ends_with("+json") => Kind::Json,
+        ("text", "html") | (_, "xhtml+xml") => Kind::Html,
+        (_, "xml") => Kind::Xml,
+        (_, s) if s.ends_with("+xml") => Kind::Xml,
+        ("text", _) | ("", _) => Kind::Text,
+        ("application", "javascript" | "x-www-form-urlencoded" | "yaml" | "x-yaml") => Kind::Text,
+        _ => Kind::Binary,
+    }
+}
+
+/// Decodes a body using its charset, defaulting to UTF-8. Returns `None` if
+/// the body doesn't look like text.
+fn decode(body: &[u8], charset: Option<&str>) -> Option<String> {
+    let encoding = charset
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8);
+
+    let (text, _, had_errors) = encoding.decode(body);
+
+    match had_errors || (encoding == UTF_8 && text.contains('\0')) {
+        true => None,
+        false => Some(text.into_owned()),
+    }
+}
+
+fn binary(mime: &str, body: &[u8]) -> String {
+    let mime = match mime {
+        "" => "unknown type",
+        mime => mime,
+    };
+
+    let mut out = format!("[binary, {}, {}]\n", size(body.len()), mime);
+
+    for (i, chunk) in body.chunks(16).take(HEX_BYTES / 16).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|b| format!("{b:02x}")).collect();
+
+        let ascii: String = chunk
+            .iter()
+            .map(|b| match b.is_ascii_graphic() || *b == b' ' {
+                true => *b as char,
+                false => '.',
+            })
+            .collect();
+
+        let _ = writeln!(out, "{:08x}  {:<47}  {}", i * 16, hex.join(" "), ascii);
+    }
+
+    if body.len() > HEX_BYTES {
+        let _ = writeln!(out, "...");
+    }
+
+    out
+}
+
+fn json(text: &str, color: bool) -> String {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+        return text.to_string();
+    };
+
+    let pretty = serde_json::to_string_pretty(&value).unwrap_or_else(|_| text.to_string());
+
+    match color {
+        true => highlight_json(&pretty),
+        false => pretty,
+    }
+}
+
+/// Highlights keys, strings and literals in pretty-printed JSON.
+fn highlight_json(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut chars = json.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '"' => {
+                let mut end = start + 1;
+                let mut escaped = false;
+
+                for (i, c) in chars.by_ref() {
+                    end = i + c.len_utf8();
+
+                    match (escaped, c) {
+                        (false, '\\') => escaped = true,
+                        (false, '"') => break,
+                        _ => escaped = false,
+                    }
+                }
+
+                let is_key = json[end..].starts_with(':');
+                let color = if is_key { BLUE } else { GREEN };
+
+                let _ = write!(out, "{}{}{}", color, &json[start..end], RESET);
+            }
+            c if c == '-' || c.is_ascii_alphanumeric() => {
+                let mut end = start + 1;
+
+                while let Some((i, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+')) {
+                        break;
+                    }
+
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+
+                let _ = write!(out, "{}{}{}", YELLOW, &json[start..end], RESET);
+            }
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Indents XML or HTML with a tag or text node per line.
+fn markup(text: &str, html: bool, color: bool) -> String {
+    let mut out = String::new();
+    let mut depth: usize = 0;
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let (node, remaining) = match rest.strip_prefix("<!--") {
+            Some(comment) => match comment.find("-->") {
+                Some(end) => rest.split_at(end + 7),
+                None => (rest, ""),
+            },
+            None if rest.starts_with('<') => match tag_end(rest) {
+                Some(end) => rest.split_at(end + 1),
+                None => (rest, ""),
+            },
+            None => rest.split_at(rest.find('<').unwrap_or(rest.len())),
+        };
+
+        rest = remaining;
+
+        if !node.starts_with('<') {
+            let text = node.trim();
+
+            if !text.is_empty() {
+                push_line(&mut out, depth, text);
+            }
+
+            continue;
+        }
+
+        let name = tag_name(node);
+
+        if node.starts_with("</") {
+            depth = depth.saturating_sub(1);
+            push_line(&mut out, depth, &highlight_tag(node, color));
+            continue;
+        }
+
+        push_line(&mut out, depth, &highlight_tag(node, color));
+
+        let is_closed = node.starts_with("<!")
+            || node.starts_with("<?")
+            || node.ends_with("/>")
+            || html && VOID_ELEMENTS.contains(&name.as_str());
+
+        if is_closed {
+            continue;
+        }
+
+        // Show content of raw elements as is, up to the closing tag
+        if html && RAW_ELEMENTS.contains(&name.as_str()) {
+            let close = format!("</{name}");
+            let end = find_ignore_ascii_case(rest, &close).unwrap_or(rest.len());
+            let (content, remaining) = rest.split_at(end);
+
+            if !content.trim().is_empty() {
+                out.push_str(content.trim_matches('\n'));
+                out.push('\n');
+            }
+
+            rest = remaining;
+        }
+
+        depth += 1;
+    }
+
+    out
+}
+
+/// Index of the first occurrence of `pattern` in `text`, ignoring ASCII case.
+fn find_ignore_ascii_case(text: &str, pattern: &str) -> Option<usize> {
+    text.char_indices().map(|(i, _)| i).find(|&i| {
+        text.get(i..i + pattern.len())
+            .is_some_and(|candidate| candidate.eq_ignore_ascii_case(pattern))
+    })
+}
+
+/// Index of the `>` closing a tag, skipping any in quoted attribute values.
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches(['<', '/'])
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// Highlights the name of a tag and its attribute values.
+fn highlight_tag(tag: &str, color: bool) -> String {
+    if !color {
+        return tag.to_string();
+    }
+
+    if tag.starts_with("<!") || tag.starts_with("<?") {
+        return format!("{DIM}{tag}{RESET}");
+    }
+
+    let start = if tag.starts_with("</") { 2 } else { 1 };
+
+    let name_end = tag[start..]
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .map_or(tag.len(), |i| start + i);
+
+    let mut out = format!("{BLUE}{}{RESET}", &tag[..name_end]);
+    let mut current = None;
+    let mut quote = None;
+
+    for c in tag[name_end..].chars() {
+        let color = match (quote, c) {
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                Some(GREEN)
+            }
+            (Some(q), c) if q == c => {
+                quote = None;
+                Some(GREEN)
+            }
+            (Some(_), _) => Some(GREEN),
+            (None, '>' | '/') => Some(BLUE),
+            (None, c) if c.is_alphanumeric() || c == '-' || c == ':' => Some(CYAN),
+            _ => None,
+        };
+
+        if color != current {
+            if current.is_some() {
+                out.push_str(RESET);
+            }
+
+            out.push_str(color.unwrap_or_default());
+            current = color;
+        }
+
+        out.push(c);
+    }
+
+    if current.is_some() {
+        out.push_str(RESET);
+    }
+
+    out
+}
+
+fn push_line(out: &mut String, depth: usize, line: &str) {
+    let _ = writeln!(out, "{}{}", "  ".repeat(depth), line);
+}
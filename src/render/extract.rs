@@ -0,0 +1,37 @@
+use crate::types::{Extract, RunReport};
+
+/// Values extracted from each response in order, using `extract` if given or
+/// otherwise the request's own `output` setting. Returns `None` if nothing
+/// is to be extracted from any response.
+pub fn values(report: &RunReport, extract: Option<&Extract>) -> Option<Vec<String>> {
+    let mut found = false;
+    let mut values = vec![];
+
+    for outcome in &report.outcomes {
+        let Some(extract) = extract.or(outcome.detail.output.as_ref()) else {
+            continue;
+        };
+
+        found = true;
+
+        if let Some(response) = &outcome.response {
+            values.extend(extract.values(response));
+        }
+    }
+
+    found.then_some(values)
+}
+
+pub fn print(report: &RunReport, extract: Option<&Extract>) -> Option<()> {
+    for value in values(report, extract)? {
+        println!("{value}");
+    }
+
+    for outcome in &report.outcomes {
+        if let Some(error) = &outcome.error {
+            eprintln!("{}: {}", outcome.name, error);
+        }
+    }
+
+    Some(())
+}
@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{json, Value};
+
+use crate::{
+    render::verbose::mask,
+    types::{RequestOutcome, RunReport},
+};
+
+/// HTTP Archive (HAR 1.2) of every request and response, with timings, as
+/// can be loaded into browser developer tools.
+///
+/// Requests which failed without a response have a status of 0, with the
+/// error in `_error`. Sensitive headers and cookies are masked as in verbose
+/// output, unless secrets are shown.
+pub fn har(report: &RunReport, show_secrets: bool) -> serde_json::Result<String> {
+    let entries: Vec<Value> = report
+        .outcomes
+        .iter()
+        .map(|outcome| entry(outcome, show_secrets))
+        .collect();
+
+    serde_json::to_string_pretty(&json!({
+        "log": {
+            "version": "1.2",
+            "creator": {
+                "name": "corkscrew",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "pages": [],
+            "entries": entries,
+        }
+    }))
+}
+
+fn entry(outcome: &RequestOutcome, show_secrets: bool) -> Value {
+    let request = &outcome.request;
+    let response = outcome.response.as_ref();
+
+    let version = response
+        .map(|r| r.version.clone())
+        .unwrap_or(String::from("HTTP/1.1"));
+
+    let query: Vec<Value> = reqwest::Url::parse(&request.url)
+        .map(|url| {
+            url.query_pairs()
+                .map(|(name, value)| json!({ "name": name, "value": value }))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Requests are always built with a body, which is `null` unless one is given
+    let has_body = outcome.detail.body.is_some() || outcome.detail.form.is_some();
+    let body = request.body.as_ref().filter(|_| has_body);
+
+    let user_content_type = outcome
+        .detail
+        .headers
+        .iter()
+        .flatten()
+        .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+
+    let request_headers: Vec<(String, String)> = request
+        .headers
+        .iter()
+        .filter(|(name, _)| has_body || user_content_type || name != "content-type")
+        .cloned()
+        .collect();
+
+    let mut har_request = json!({
+        "method": request.method,
+        "url": request.url,
+        "httpVersion": version,
+        "cookies": cookies(request.header("cookie"), show_secrets),
+        "headers": headers(&request_headers, show_secrets),
+        "queryString": query,
+        "headersSize": -1,
+        "bodySize": body.map_or(0, |body| body.len()),
+    });
+
+    if let Some(body) = body {
+        har_request["postData"] = json!({
+            "mimeType": request.header("content-type").unwrap_or_default(),
+            "text": String::from_utf8_lossy(body),
+        });
+    }
+
+    let har_response = match response {
+        Some(response) => {
+            let mut content = json!({
+                "size": response.size(),
+                "mimeType": response.header("content-type").unwrap_or_default(),
+            });
+
+            match (&response.saved, std::str::from_utf8(&response.body)) {
+                (Some(saved), _) => {
+                    content["comment"] = json!(format!("Saved to {}", saved.path.display()))
+                }
+                (None, Ok(text)) => content["text"] = json!(text),
+                (None, Err(_)) => {
+                    content["text"] = json!(STANDARD.encode(&response.body));
+                    content["encoding"] = json!("base64");
+                }
+            }
+
+            json!({
+                "status": response.status,
+                "statusText": reqwest::StatusCode::from_u16(response.status)
+                    .ok()
+                    .and_then(|status| status.canonical_reason())
+                    .unwrap_or_default(),
+                "httpVersion": response.version,
+                "cookies": [],
+                "headers": headers(&response.headers, show_secrets),
+                "content": content,
+                "redirectURL": response.header("location").unwrap_or_default(),
+                "headersSize": -1,
+                "bodySize": response.size(),
+            })
+        }
+        None => json!({
+            "status": 0,
+            "statusText": "",
+            "httpVersion": version,
+            "cookies": [],
+            "headers": [],
+            "content": { "size": 0, "mimeType": "" },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+            "_error": outcome.error,
+        }),
+    };
+
+    let timings = &outcome.timings;
+    let dns = timings.dns.unwrap_or_default();
+
+    // Requests which failed aren't timed, so spent all their time waiting
+    let (time, wait) = match response {
+        Some(_) => (timings.total, timings.ttfb.saturating_sub(dns)),
+        None => (outcome.duration, outcome.duration),
+    };
+
+    json!({
+        "startedDateTime": humantime::format_rfc3339_millis(outcome.started).to_string(),
+        "time": ms(time),
+        "request": har_request,
+        "response": har_response,
+        "cache": {},
+        // Connecting and sending aren't timed separately, so are included in waiting
+        "timings": {
+            "blocked": -1,
+            "dns": timings.dns.map_or(-1.0, ms),
+            "connect": -1,
+            "ssl": -1,
+            "send": 0,
+            "wait": ms(wait),
+            "receive": ms(timings.download),
+        },
+        "comment": outcome.name,
+    })
+}
+
+fn headers(headers: &[(String, String)], show_secrets: bool) -> Vec<Value> {
+    headers
+        .iter()
+        .map(|(name, value)| json!({ "name": name, "value": mask(name, value, show_secrets) }))
+        .collect()
+}
+
+fn cookies(header: Option<&str>, show_secrets: bool) -> Vec<Value> {
+    header
+        .unwrap_or_default()
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .map(|(name, value)| json!({ "name": name, "value": mask("cookie", value, show_secrets) }))
+        .collect()
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
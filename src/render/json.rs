@@ -0,0 +1,89 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::types::{RequestOutcome, RunReport};
+
+/// Structured record of a request outcome, for machine-readable output.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Record {
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub headers: Map<String, Value>,
+    /// Response body, as JSON if it can be parsed as JSON, otherwise as text.
+    pub body: Option<Value>,
+    /// Path the response body was saved to, in place of including it.
+    pub saved_to: Option<String>,
+    pub attempts: u32,
+    pub timings: RecordTimings,
+    pub error: Option<String>,
+}
+
+/// Timings in milliseconds.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct RecordTimings {
+    pub dns: Option<f64>,
+    pub ttfb: f64,
+    pub download: f64,
+    pub total: f64,
+    pub duration: f64,
+}
+
+impl From<&RequestOutcome> for Record {
+    fn from(outcome: &RequestOutcome) -> Self {
+        let response = outcome.response.as_ref();
+
+        let mut headers = Map::new();
+
+        for (name, value) in response.map(|r| r.headers.as_slice()).unwrap_or_default() {
+            let value = match headers.remove(name) {
+                Some(Value::String(existing)) => format!("{existing}, {value}"),
+                _ => value.clone(),
+            };
+
+            headers.insert(name.clone(), Value::String(value));
+        }
+
+        let body = response
+            .filter(|r| r.saved.is_none())
+            .map(|r| serde_json::from_slice(&r.body).unwrap_or_else(|_| Value::String(r.text())));
+
+        let saved_to = response
+            .and_then(|r| r.saved.as_ref())
+            .map(|saved| saved.path.display().to_string());
+
+        let ms = |duration: std::time::Duration| duration.as_secs_f64() * 1000.0;
+
+        Record {
+            name: outcome.name.clone(),
+            method: outcome.request.method.clone(),
+            url: outcome.request.url.clone(),
+            status: response.map(|r| r.status),
+            headers,
+            body,
+            saved_to,
+            attempts: outcome.attempts,
+            timings: RecordTimings {
+                dns: outcome.timings.dns.map(ms),
+                ttfb: ms(outcome.timings.ttfb),
+                download: ms(outcome.timings.download),
+                total: ms(outcome.timings.total),
+                duration: ms(outcome.duration),
+            },
+            error: outcome.error.clone(),
+        }
+    }
+}
+
+/// Pretty-printed JSON array of records for all outcomes.
+pub fn json(report: &RunReport) -> serde_json::Result<String> {
+    let records: Vec<Record> = report.outcomes.iter().map(Record::from).collect();
+
+    serde_json::to_string_pretty(&records)
+}
+
+/// Single line JSON record for an outcome.
+pub fn ndjson(outcome: &RequestOutcome) -> serde_json::Result<String> {
+    serde_json::to_string(&Record::from(outcome))
+}
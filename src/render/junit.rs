@@ -0,0 +1,100 @@
+use std::fmt::Write;
+
+use crate::types::{RequestOutcome, RunReport};
+
+/// JUnit XML report with a test case per request, grouped into a test suite
+/// per group of requests.
+pub fn junit(report: &RunReport) -> String {
+    let mut suites: Vec<(String, Vec<&RequestOutcome>)> = vec![];
+
+    for outcome in &report.outcomes {
+        let suite = match outcome.groups() {
+            [] => String::from("corkscrew"),
+            groups => groups.join("."),
+        };
+
+        match suites.iter_mut().find(|(name, _)| *name == suite) {
+            Some((_, outcomes)) => outcomes.push(outcome),
+            None => suites.push((suite, vec![outcome])),
+        }
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"corkscrew\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">",
+        report.outcomes.len(),
+        report.outcomes.iter().filter(|o| is_failure(o)).count(),
+        report.outcomes.iter().filter(|o| o.error.is_some()).count(),
+        report
+            .outcomes
+            .iter()
+            .map(|o| o.duration.as_secs_f64())
+            .sum::<f64>(),
+    );
+
+    for (suite, outcomes) in suites {
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">",
+            escape(&suite),
+            outcomes.len(),
+            outcomes.iter().filter(|o| is_failure(o)).count(),
+            outcomes.iter().filter(|o| o.error.is_some()).count(),
+            outcomes
+                .iter()
+                .map(|o| o.duration.as_secs_f64())
+                .sum::<f64>(),
+        );
+
+        for outcome in outcomes {
+            let _ = write!(
+                xml,
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                escape(&outcome.name),
+                escape(&suite),
+                outcome.duration.as_secs_f64(),
+            );
+
+            match (&outcome.response, &outcome.error) {
+                (_, Some(error)) => {
+                    let _ = write!(
+                        xml,
+                        ">\n      <error type=\"request\" message=\"{}\"/>\n    </testcase>\n",
+                        escape(error)
+                    );
+                }
+                (Some(response), None) if is_failure(outcome) => {
+                    let _ = write!(
+                        xml,
+                        ">\n      <failure type=\"status\" message=\"{} {} returned {}\"/>\n    </testcase>\n",
+                        escape(&outcome.request.method),
+                        escape(&outcome.request.url),
+                        response.status
+                    );
+                }
+                _ => xml.push_str("/>\n"),
+            }
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+
+    xml
+}
+
+fn is_failure(outcome: &RequestOutcome) -> bool {
+    outcome.error.is_none() && !outcome.is_success()
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
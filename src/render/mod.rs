@@ -0,0 +1,8 @@
+pub mod body;
+pub mod extract;
+pub mod har;
+pub mod json;
+pub mod junit;
+pub mod table;
+pub mod tap;
+pub mod verbose;
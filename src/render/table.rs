@@ -0,0 +1,25 @@
+use cli_table::{ColorChoice, TableStruct, WithTitle};
+use std::io;
+
+use crate::types::{Output, RunReport};
+
+pub fn table(report: &RunReport, color: bool) -> TableStruct {
+    let rows: Vec<Output> = report.outcomes.iter().map(Output::from).collect();
+
+    rows.with_title().color_choice(match color {
+        true => ColorChoice::Auto,
+        false => ColorChoice::Never,
+    })
+}
+
+pub fn print(report: &RunReport, color: bool) -> io::Result<()> {
+    cli_table::print_stdout(table(report, color))?;
+
+    for outcome in &report.outcomes {
+        if let Some(error) = &outcome.error {
+            eprintln!("{}: {}", outcome.name, error);
+        }
+    }
+
+    Ok(())
+}
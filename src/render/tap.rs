@@ -0,0 +1,53 @@
+use std::fmt::Write;
+
+use crate::types::RunReport;
+
+/// TAP version 13 report with a test point per request.
+pub fn tap(report: &RunReport) -> String {
+    let mut tap = format!("TAP version 13\n1..{}\n", report.outcomes.len());
+
+    for (i, outcome) in report.outcomes.iter().enumerate() {
+        let result = match outcome.is_success() {
+            true => "ok",
+            false => "not ok",
+        };
+
+        let _ = writeln!(tap, "{} {} - {}", result, i + 1, outcome.path.join("."));
+
+        if outcome.is_success() {
+            continue;
+        }
+
+        tap.push_str("  ---\n");
+
+        match (&outcome.response, &outcome.error) {
+            (_, Some(error)) => {
+                let _ = writeln!(tap, "  message: {}", yaml_string(error));
+                tap.push_str("  severity: error\n");
+            }
+            (Some(response), None) => {
+                let _ = writeln!(
+                    tap,
+                    "  message: {}",
+                    yaml_string(&format!(
+                        "{} {} returned {}",
+                        outcome.request.method, outcome.request.url, response.status
+                    ))
+                );
+                tap.push_str("  severity: fail\n");
+                let _ = writeln!(tap, "  status: {}", response.status);
+            }
+            (None, None) => {}
+        }
+
+        let _ = writeln!(tap, "  duration_ms: {}", outcome.duration.as_millis());
+
+        tap.push_str("  ...\n");
+    }
+
+    tap
+}
+
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
@@ -0,0 +1,99 @@
+use std::fmt::Write;
+
+use reqwest::StatusCode;
+
+use crate::{render::body, types::RequestOutcome};
+
+/// Headers whose values are masked unless secrets are shown.
+const SENSITIVE_HEADERS: [&str; 6] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-auth-token",
+];
+
+/// Request and response for an outcome in the style of `curl -v`, with lines
+/// sent prefixed by `>` and lines received prefixed by `<`. Bodies are only
+/// included from level 2, formatted by their content type.
+pub fn verbose(outcome: &RequestOutcome, level: u8, show_secrets: bool, color: bool) -> String {
+    let mut out = String::new();
+    let request = &outcome.request;
+
+    let _ = writeln!(out, "* {}", outcome.name);
+    let _ = writeln!(out, "> {} {}", request.method, request.url);
+
+    for (name, value) in &request.headers {
+        let _ = writeln!(out, "> {}: {}", name, mask(name, value, show_secrets));
+    }
+
+    out.push_str(">\n");
+
+    if let (2.., Some(body)) = (level, &request.body) {
+        push_body(&mut out, &request.headers, body, color);
+    }
+
+    if let Some(error) = &outcome.error {
+        let _ = writeln!(out, "* {}", error);
+    }
+
+    if let Some(response) = &outcome.response {
+        let reason = StatusCode::from_u16(response.status)
+            .ok()
+            .and_then(|s| s.canonical_reason())
+            .unwrap_or_default();
+
+        let _ = writeln!(out, "< {} {}", response.status, reason);
+
+        if response.url != request.url {
+            let _ = writeln!(out, "* redirected to {}", response.url);
+        }
+
+        for (name, value) in &response.headers {
+            let _ = writeln!(out, "< {}: {}", name, mask(name, value, show_secrets));
+        }
+
+        out.push_str("<\n");
+
+        if let Some(saved) = &response.saved {
+            let _ = writeln!(
+                out,
+                "* saved {} bytes to {}",
+                saved.size,
+                saved.path.display()
+            );
+        } else if level >= 2 {
+            push_body(&mut out, &response.headers, &response.body, color);
+        }
+    }
+
+    out
+}
+
+fn push_body(out: &mut String, headers: &[(String, String)], body: &[u8], color: bool) {
+    if body.is_empty() {
+        return;
+    }
+
+    out.push_str(&body::body(headers, body, color));
+
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Masks the value of a sensitive header, keeping any authorization scheme,
+/// e.g. `Bearer ********`.
+pub(crate) fn mask(name: &str, value: &str, show_secrets: bool) -> String {
+    if show_secrets || !SENSITIVE_HEADERS.contains(&name.to_lowercase().as_str()) {
+        return value.to_string();
+    }
+
+    match value.split_once(' ') {
+        Some((scheme, _)) if name.to_lowercase().ends_with("authorization") => {
+            format!("{scheme} ********")
+        }
+        _ => String::from("********"),
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchReport {
+    pub name: String,
+    pub requests: u64,
+    pub errors: u64,
+    pub elapsed: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl BenchReport {
+    /// Summarises the latencies of completed requests. Requests that failed
+    /// or returned a non-success status are counted as errors.
+    pub fn new(
+        name: String,
+        mut latencies: Vec<Duration>,
+        errors: u64,
+        requests: u64,
+        elapsed: Duration,
+    ) -> BenchReport {
+        latencies.sort();
+
+        BenchReport {
+            name,
+            requests,
+            errors,
+            elapsed,
+            p50: percentile(&latencies, 50.0),
+            p90: percentile(&latencies, 90.0),
+            p99: percentile(&latencies, 99.0),
+            max: latencies.last().copied().unwrap_or_default(),
+        }
+    }
+
+    /// Requests per second.
+    pub fn throughput(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            secs if secs > 0.0 => self.requests as f64 / secs,
+            _ => 0.0,
+        }
+    }
+
+    pub fn error_rate(&self) -> f64 {
+        match self.requests {
+            0 => 0.0,
+            requests => self.errors as f64 / requests as f64,
+        }
+    }
+}
+
+/// Nearest-rank percentile of already sorted latencies.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::default();
+    }
+
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod test {
+    use crate::BenchReport;
+    use std::{error::Error, time::Duration};
+
+    #[test]
+    fn test_bench_report_percentiles() -> Result<(), Box<dyn Error>> {
+        let latencies = (1..=100).rev().map(Duration::from_millis).collect();
+
+        let got = BenchReport::new(
+            String::from("test"),
+            latencies,
+            5,
+            100,
+            Duration::from_secs(4),
+        );
+
+        assert_eq!(got.p50, Duration::from_millis(50));
+        assert_eq!(got.p90, Duration::from_millis(90));
+        assert_eq!(got.p99, Duration::from_millis(99));
+        assert_eq!(got.max, Duration::from_millis(100));
+        assert_eq!(got.throughput(), 25.0, "should be requests per second");
+        assert_eq!(got.error_rate(), 0.05);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bench_report_without_latencies() -> Result<(), Box<dyn Error>> {
+        let got = BenchReport::new(String::from("test"), vec![], 3, 3, Duration::ZERO);
+
+        assert_eq!(got.p50, Duration::ZERO);
+        assert_eq!(got.max, Duration::ZERO);
+        assert_eq!(got.throughput(), 0.0);
+        assert_eq!(got.error_rate(), 1.0);
+
+        Ok(())
+    }
+}
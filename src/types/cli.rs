@@ -1,6 +1,9 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr, time::Duration};
 
-use clap::Parser;
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{JsonPath, Rate};
 
 #[derive(Parser)]
 #[clap(
@@ -9,6 +12,12 @@ use clap::Parser;
     about = "Configure HTTP requests in YAML and execute from the command line."
 )]
 pub struct Cli {
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
+    #[clap(
+        help = "Requests or groups to run, by name or path, e.g. get_user, users:* or '**:delete_*'"
+    )]
     pub request_names: Vec<String>,
 
     #[clap(
@@ -16,6 +25,7 @@ pub struct Cli {
         long = "file",
         default_value = "requests.yml",
         name = "file_path",
+        global = true,
         help = "Path to file containing requests"
     )]
     pub config_path: Option<PathBuf>,
@@ -24,7 +34,307 @@ pub struct Cli {
         short = 'p',
         long = "parallel",
         name = "num",
+        global = true,
         help = "Specify number of parallel requests"
     )]
     pub parallel: Option<usize>,
+
+    #[clap(
+        short = 'r',
+        long = "rate",
+        name = "n/s",
+        global = true,
+        help = "Maximum rate of requests to each host, e.g. 10/s, 100/m"
+    )]
+    pub rate: Option<Rate>,
+
+    #[clap(long = "no-color", global = true, help = "Disable coloured output")]
+    pub no_color: bool,
+
+    #[clap(
+        short = 'o',
+        long = "output",
+        name = "format",
+        global = true,
+        help = "Format to output results in, ndjson streams a result per line [default: table]"
+    )]
+    pub output: Option<OutputFormat>,
+
+    #[clap(
+        long = "report",
+        name = "format[=path]",
+        global = true,
+        help = "Write a test report, e.g. junit=report.xml or tap, to stdout if no path"
+    )]
+    pub reports: Vec<Report>,
+
+    #[clap(
+        short = 'v',
+        long = "verbose",
+        action = ArgAction::Count,
+        global = true,
+        help = "Print request and response headers, -vv to include bodies"
+    )]
+    pub verbose: u8,
+
+    #[clap(
+        long = "show-secrets",
+        global = true,
+        help = "Show sensitive header values, such as Authorization, in verbose output and HAR files"
+    )]
+    pub show_secrets: bool,
+
+    #[clap(
+        long = "select",
+        name = "jsonpath",
+        global = true,
+        help = "Print only values selected from response bodies, e.g. '$.data[*].id'"
+    )]
+    pub select: Option<JsonPath>,
+
+    #[clap(
+        long = "header-select",
+        name = "header",
+        global = true,
+        help = "Print only the value of a response header, e.g. Location"
+    )]
+    pub header_select: Option<String>,
+
+    #[clap(
+        long = "output-dir",
+        name = "dir",
+        global = true,
+        help = "Save response bodies to files in a directory, named after each request"
+    )]
+    pub output_dir: Option<PathBuf>,
+
+    #[clap(
+        long = "save-headers",
+        global = true,
+        help = "Write response status and headers alongside saved bodies"
+    )]
+    pub save_headers: bool,
+
+    #[clap(
+        long = "har",
+        name = "path",
+        global = true,
+        help = "Write requests and responses, with timings, to an HTTP Archive (HAR) file"
+    )]
+    pub har: Option<PathBuf>,
+
+    #[clap(
+        long = "allow-duplicates",
+        global = true,
+        help = "Run only the last of any requests with the same name, rather than erroring"
+    )]
+    pub allow_duplicates: bool,
+
+    #[clap(
+        long = "tag",
+        name = "tag",
+        global = true,
+        help = "Only run requests with a tag, e.g. smoke, or any of several if repeated"
+    )]
+    pub tags: Vec<String>,
+
+    #[clap(
+        long = "exclude-tag",
+        name = "excluded_tag",
+        value_name = "tag",
+        global = true,
+        help = "Don't run requests with a tag, e.g. destructive"
+    )]
+    pub exclude_tags: Vec<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Ndjson,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Junit,
+    Tap,
+}
+
+/// A test report to write, written as `<format>[=<path>]`, e.g. `junit=report.xml`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Report {
+    pub format: ReportFormat,
+    /// Where to write the report, or stdout if none.
+    pub path: Option<PathBuf>,
+}
+
+impl FromStr for Report {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (format, path) = match value.split_once('=') {
+            Some((format, path)) => (format, Some(PathBuf::from(path))),
+            None => (value, None),
+        };
+
+        let format = match format.trim().to_lowercase().as_str() {
+            "junit" => ReportFormat::Junit,
+            "tap" => ReportFormat::Tap,
+            _ => {
+                return Err(format!(
+                    "Invalid report '{value}', expected e.g. 'junit=report.xml' or 'tap'."
+                ))
+            }
+        };
+
+        Ok(Report { format, path })
+    }
+}
+
+#[derive(Subcommand, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum Command {
+    #[clap(about = "Repeatedly execute requests and report throughput and latency")]
+    Bench(Bench),
+
+    #[clap(about = "Print requests in another format, e.g. curl or .http")]
+    Export(Export),
+
+    #[clap(
+        about = "Convert requests from another format, e.g. curl, HAR, .http, Postman or OpenAPI"
+    )]
+    Import(Import),
+
+    #[clap(about = "Check requests for problems without running them, e.g. a missing host")]
+    Validate,
+}
+
+#[derive(Args, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Export {
+    pub request_names: Vec<String>,
+
+    #[clap(
+        long = "format",
+        name = "export_format",
+        value_name = "format",
+        default_value = "curl",
+        help = "Format to export requests in"
+    )]
+    pub format: ExportFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Curl,
+    Http,
+}
+
+#[derive(Args, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Import {
+    #[clap(
+        name = "import_format",
+        value_name = "format",
+        help = "Format to import from"
+    )]
+    pub format: ImportFormat,
+
+    #[clap(
+        name = "input",
+        help = "Path to file to import from, or - to read from stdin [default: stdin]"
+    )]
+    pub path: Option<PathBuf>,
+
+    #[clap(
+        long = "into",
+        name = "requests_file",
+        help = "Merge imported requests into a file, rather than printing them"
+    )]
+    pub into: Option<PathBuf>,
+
+    #[clap(
+        long = "group",
+        name = "group_name",
+        help = "Name of the group to nest imported requests under, created if missing"
+    )]
+    pub group: Option<String>,
+
+    #[clap(
+        long = "host",
+        name = "host_pattern",
+        help = "Only import HAR requests to a host, e.g. '*.example.com'"
+    )]
+    pub hosts: Vec<String>,
+
+    #[clap(
+        long = "method",
+        name = "http_method",
+        help = "Only import HAR requests with a method, e.g. POST"
+    )]
+    pub methods: Vec<String>,
+
+    #[clap(
+        long = "url",
+        name = "url_pattern",
+        help = "Only import HAR requests with a URL matching a pattern, e.g. '*/api/*'"
+    )]
+    pub urls: Vec<String>,
+
+    #[clap(
+        long = "strip-cookies",
+        help = "Remove Cookie headers from imported HAR requests"
+    )]
+    pub strip_cookies: bool,
+
+    #[clap(
+        long = "strip-auth",
+        help = "Remove Authorization headers from imported HAR requests"
+    )]
+    pub strip_auth: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    Curl,
+    Har,
+    Http,
+    Postman,
+    #[value(name = "openapi")]
+    #[serde(rename = "openapi")]
+    OpenApi,
+}
+
+#[derive(Args, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Bench {
+    pub request_names: Vec<String>,
+
+    #[clap(
+        short = 'n',
+        long = "repeat",
+        name = "count",
+        help = "Number of times to execute each request [default: 10, unless --duration is given]"
+    )]
+    pub repeat: Option<u64>,
+
+    #[clap(
+        short = 'd',
+        long = "duration",
+        name = "time",
+        value_parser = humantime::parse_duration,
+        help = "How long to execute each request for, e.g. 30s, 2m"
+    )]
+    pub duration: Option<Duration>,
+
+    #[clap(
+        short = 'c',
+        long = "concurrency",
+        name = "workers",
+        default_value = "1",
+        help = "Number of requests to have in flight at once"
+    )]
+    pub concurrency: usize,
 }
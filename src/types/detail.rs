@@ -1,25 +1,65 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
-use crate::types::auth::AuthType;
-use std::collections::HashMap;
+use crate::types::{
+    auth::AuthType,
+    extract::Extract,
+    inherit::{Inherit, Unset},
+    rate::Rate,
+    retry::Retry,
+};
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct Detail {
-    pub requests: Option<Vec<Detail>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted")]
+    pub variables: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub scheme: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resource: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted")]
     pub params: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted")]
     pub headers: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub auth: Option<AuthType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted")]
     pub form: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<Retry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<Rate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Extract>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub save_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub save_headers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherit: Option<Inherit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unset: Option<Unset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests: Option<Vec<Detail>>,
 }
 
 impl Default for Detail {
@@ -32,6 +72,15 @@ impl Detail {
     pub fn new() -> Detail {
         Detail {
             requests: None,
+            inherit: None,
+            unset: None,
+            tags: None,
+            variables: None,
+            save_headers: None,
+            save_to: None,
+            output: None,
+            rate_limit: None,
+            retry: None,
             name: None,
             host: None,
             scheme: None,
@@ -50,6 +99,16 @@ impl Detail {
     }
 }
 
+/// Serializes a map sorted by key, so written config is stable.
+fn sorted<S: Serializer>(
+    map: &Option<HashMap<String, String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    map.as_ref()
+        .map(|map| map.iter().collect::<BTreeMap<_, _>>())
+        .serialize(serializer)
+}
+
 #[cfg(test)]
 mod test {
     use crate::Detail;
@@ -61,6 +120,15 @@ mod test {
 
         let expected = Detail {
             requests: None,
+            inherit: None,
+            unset: None,
+            tags: None,
+            variables: None,
+            save_headers: None,
+            save_to: None,
+            output: None,
+            rate_limit: None,
+            retry: None,
             name: None,
             host: None,
             scheme: None,
@@ -91,6 +159,15 @@ mod test {
 
         let expected = Detail {
             requests: None,
+            inherit: None,
+            unset: None,
+            tags: None,
+            variables: None,
+            save_headers: None,
+            save_to: None,
+            output: None,
+            rate_limit: None,
+            retry: None,
             name: None,
             host: None,
             scheme: None,
@@ -0,0 +1,14 @@
+use crate::types::{ResponseSnapshot, Timings};
+use std::time::{Duration, SystemTime};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Execution {
+    pub response: Option<ResponseSnapshot>,
+    pub error: Option<String>,
+    pub attempts: u32,
+    pub timings: Timings,
+    /// When the first attempt was sent.
+    pub started: SystemTime,
+    /// Time taken to execute the request, including any retries.
+    pub duration: Duration,
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::{JsonPath, ResponseSnapshot};
+
+/// Values to extract from a response, printed one per line in place of the
+/// usual output.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Extract {
+    /// JSONPath selecting values from a JSON body.
+    pub select: Option<JsonPath>,
+    /// Name of a header to extract the value of.
+    pub header: Option<String>,
+}
+
+impl Default for Extract {
+    fn default() -> Extract {
+        Extract::new()
+    }
+}
+
+impl Extract {
+    pub fn new() -> Extract {
+        Extract {
+            select: None,
+            header: None,
+        }
+    }
+
+    /// Extracted values, headers first, then any selected from the body.
+    /// Strings are returned as is and any other JSON values as compact JSON.
+    pub fn values(&self, response: &ResponseSnapshot) -> Vec<String> {
+        let mut values: Vec<String> = match &self.header {
+            Some(name) => response
+                .headers
+                .iter()
+                .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.clone())
+                .collect(),
+            None => vec![],
+        };
+
+        if let Some(path) = &self.select {
+            if let Ok(body) = serde_json::from_slice::<Value>(&response.body) {
+                values.extend(path.select(&body).into_iter().map(|value| match value {
+                    Value::String(s) => s.clone(),
+                    value => value.to_string(),
+                }));
+            }
+        }
+
+        values
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Extract, ResponseSnapshot};
+    use std::error::Error;
+
+    #[test]
+    fn test_extract_values() -> Result<(), Box<dyn Error>> {
+        let response = ResponseSnapshot {
+            status: 201,
+            version: String::from("HTTP/1.1"),
+            url: String::from("http://localhost/users"),
+            headers: vec![(String::from("location"), String::from("/users/3"))],
+            body: br#"{"data": [{"id": 3, "name": "three", "tags": ["a"]}]}"#.to_vec(),
+            saved: None,
+        };
+
+        let extract = Extract {
+            select: Some("$.data[*].name".parse()?),
+            header: Some(String::from("Location")),
+        };
+
+        assert_eq!(
+            extract.values(&response),
+            vec![String::from("/users/3"), String::from("three")]
+        );
+
+        let extract = Extract {
+            select: Some("$.data[0].tags".parse()?),
+            ..Extract::new()
+        };
+
+        assert_eq!(
+            extract.values(&response),
+            vec![String::from(r#"["a"]"#)],
+            "should print non-string values as JSON"
+        );
+
+        Ok(())
+    }
+}
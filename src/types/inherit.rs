@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+/// How a request inherits headers, params, form data and its body from the
+/// requests it's nested in, where its own settings are merged with those it
+/// inherits unless set otherwise.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Inherit {
+    /// How headers are inherited (default: merge).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Inheritance>,
+    /// How params are inherited (default: merge).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Inheritance>,
+    /// How form data is inherited (default: merge).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form: Option<Inheritance>,
+    /// How a JSON body is inherited (default: replace).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Inheritance>,
+}
+
+/// How a setting is inherited, written as `merge`, `replace` or `false`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "InheritanceValue", into = "InheritanceValue")]
+pub enum Inheritance {
+    /// Its own keys are added to those inherited, replacing any with the same
+    /// name. JSON bodies are merged as a JSON merge patch, so objects are
+    /// merged and `null` removes a key.
+    Merge,
+    /// Its own value replaces the inherited value, which is only used if it
+    /// doesn't have one.
+    Replace,
+    /// Nothing is inherited.
+    None,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum InheritanceValue {
+    Enabled(bool),
+    Name(String),
+}
+
+impl TryFrom<InheritanceValue> for Inheritance {
+    type Error = String;
+
+    fn try_from(value: InheritanceValue) -> Result<Self, Self::Error> {
+        match value {
+            InheritanceValue::Enabled(true) => Ok(Inheritance::Merge),
+            InheritanceValue::Enabled(false) => Ok(Inheritance::None),
+            InheritanceValue::Name(name) => match name.as_str() {
+                "merge" => Ok(Inheritance::Merge),
+                "replace" => Ok(Inheritance::Replace),
+                "none" => Ok(Inheritance::None),
+                _ => Err(format!(
+                    "Invalid inheritance '{name}', expected merge, replace or false."
+                )),
+            },
+        }
+    }
+}
+
+impl From<Inheritance> for InheritanceValue {
+    fn from(value: Inheritance) -> Self {
+        match value {
+            Inheritance::Merge => InheritanceValue::Name(String::from("merge")),
+            Inheritance::Replace => InheritanceValue::Name(String::from("replace")),
+            Inheritance::None => InheritanceValue::Enabled(false),
+        }
+    }
+}
+
+/// Inherited keys to remove from a request's headers, params and form data.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Unset {
+    pub headers: Option<Vec<String>>,
+    pub params: Option<Vec<String>>,
+    pub form: Option<Vec<String>>,
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use crate::{Inherit, Inheritance};
+
+    #[test]
+    fn test_deserialize_inheritance() -> Result<(), Box<dyn Error>> {
+        let got: Inherit =
+            serde_yaml::from_str("{ headers: false, params: replace, form: merge, body: true }")?;
+
+        assert_eq!(
+            got,
+            Inherit {
+                headers: Some(Inheritance::None),
+                params: Some(Inheritance::Replace),
+                form: Some(Inheritance::Merge),
+                body: Some(Inheritance::Merge),
+            }
+        );
+
+        assert!(
+            serde_yaml::from_str::<Inherit>("{ headers: append }").is_err(),
+            "should reject unknown inheritance"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{fmt, str::FromStr};
+
+/// A JSONPath expression selecting values from a JSON document, e.g.
+/// `$.data[*].id`.
+///
+/// Supports child names (`.name` or `['name']`), array indices including
+/// negative indices from the end (`[0]`, `[-1]`), wildcards (`.*` or `[*]`)
+/// and recursive descent (`..name`).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct JsonPath {
+    path: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(i64),
+    Wildcard,
+    /// The current values and all of their descendants.
+    Descendants,
+}
+
+impl JsonPath {
+    /// Values matching the path, in document order.
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        self.segments
+            .iter()
+            .fold(vec![value], |values, segment| match segment {
+                Segment::Key(key) => values.into_iter().filter_map(|v| v.get(key)).collect(),
+                Segment::Index(index) => values
+                    .into_iter()
+                    .filter_map(|v| {
+                        let items = v.as_array()?;
+
+                        let index = match *index < 0 {
+                            true => items.len().checked_sub(index.unsigned_abs() as usize)?,
+                            false => *index as usize,
+                        };
+
+                        items.get(index)
+                    })
+                    .collect(),
+                Segment::Wildcard => values.into_iter().flat_map(children).collect(),
+                Segment::Descendants => values.into_iter().flat_map(descendants).collect(),
+            })
+    }
+}
+
+fn children(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(entries) => entries.values().collect(),
+        _ => vec![],
+    }
+}
+
+fn descendants(value: &Value) -> Vec<&Value> {
+    let mut values = vec![value];
+
+    for child in children(value) {
+        values.extend(descendants(child));
+    }
+
+    values
+}
+
+impl FromStr for JsonPath {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| format!("Invalid JSONPath '{value}', {reason}.");
+
+        let mut rest = value
+            .trim()
+            .strip_prefix('$')
+            .ok_or_else(|| invalid("expected it to start with '$'"))?;
+
+        let mut segments = vec![];
+
+        while !rest.is_empty() {
+            if let Some(descendant) = rest.strip_prefix("..") {
+                segments.push(Segment::Descendants);
+
+                rest = match descendant.starts_with('[') {
+                    true => descendant,
+                    false => &rest[1..],
+                };
+            } else if let Some(child) = rest.strip_prefix('.') {
+                let end = child.find(['.', '[']).unwrap_or(child.len());
+
+                segments.push(match &child[..end] {
+                    "" => return Err(invalid("expected a name after '.'")),
+                    "*" => Segment::Wildcard,
+                    key => Segment::Key(key.to_string()),
+                });
+
+                rest = &child[end..];
+            } else if let Some(bracket) = rest.strip_prefix('[') {
+                let end = bracket.find(']').ok_or_else(|| invalid("missing ']'"))?;
+
+                let selector = bracket[..end].trim();
+
+                let quoted = ['\'', '"']
+                    .iter()
+                    .find_map(|q| selector.strip_prefix(*q).and_then(|s| s.strip_suffix(*q)));
+
+                segments.push(match (selector, quoted) {
+                    (_, Some(key)) => Segment::Key(key.to_string()),
+                    ("*", None) => Segment::Wildcard,
+                    (index, None) => Segment::Index(
+                        index
+                            .parse()
+                            .map_err(|_| invalid(&format!("unsupported selector '[{index}]'")))?,
+                    ),
+                });
+
+                rest = &bracket[end + 1..];
+            } else {
+                return Err(invalid("expected '.' or '['"));
+            }
+        }
+
+        Ok(JsonPath {
+            path: value.trim().to_string(),
+            segments,
+        })
+    }
+}
+
+impl TryFrom<String> for JsonPath {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<JsonPath> for String {
+    fn from(value: JsonPath) -> Self {
+        value.path
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::JsonPath;
+    use serde_json::{json, Value};
+    use std::error::Error;
+
+    fn select(path: &str, value: &Value) -> Result<Vec<Value>, Box<dyn Error>> {
+        let path: JsonPath = path.parse()?;
+
+        Ok(path.select(value).into_iter().cloned().collect())
+    }
+
+    #[test]
+    fn test_json_path_select() -> Result<(), Box<dyn Error>> {
+        let value = json!({
+            "data": [
+                { "id": 1, "name": "one", "tags": { "a": true } },
+                { "id": 2, "name": "two" }
+            ],
+            "meta": { "total": 2, "next page": null }
+        });
+
+        assert_eq!(select("$", &value)?, vec![value.clone()]);
+        assert_eq!(select("$.data[*].id", &value)?, vec![json!(1), json!(2)]);
+        assert_eq!(select("$.data[1].name", &value)?, vec![json!("two")]);
+        assert_eq!(select("$.data[-1].id", &value)?, vec![json!(2)]);
+        assert_eq!(select("$['meta']['next page']", &value)?, vec![json!(null)]);
+        assert_eq!(select("$.meta.*", &value)?, vec![json!(null), json!(2)]);
+        assert_eq!(select("$..id", &value)?, vec![json!(1), json!(2)]);
+        assert_eq!(select("$..[0].tags.a", &value)?, vec![json!(true)]);
+        assert_eq!(
+            select("$.data[5].id", &value)?,
+            vec![] as Vec<Value>,
+            "should select nothing out of bounds"
+        );
+        assert_eq!(select("$.missing", &value)?, vec![] as Vec<Value>);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid_json_path() -> Result<(), Box<dyn Error>> {
+        assert!("data.id".parse::<JsonPath>().is_err(), "should require '$'");
+        assert!("$.data[".parse::<JsonPath>().is_err(), "should require ']'");
+        assert!(
+            "$.data[1:2]".parse::<JsonPath>().is_err(),
+            "should reject slices"
+        );
+        assert!("$.".parse::<JsonPath>().is_err(), "should require a name");
+        assert!("$data".parse::<JsonPath>().is_err());
+
+        Ok(())
+    }
+}
@@ -15,13 +15,16 @@ impl Index<&'_ str> for Method {
             "put" => &reqwest::Method::PUT,
             "patch" => &reqwest::Method::PATCH,
             "delete" => &reqwest::Method::DELETE,
+            "head" => &reqwest::Method::HEAD,
+            "options" => &reqwest::Method::OPTIONS,
+            "trace" => &reqwest::Method::TRACE,
+            "connect" => &reqwest::Method::CONNECT,
             _ => &reqwest::Method::GET,
         }
     }
 }
 
 #[cfg(test)]
-
 mod test {
     use std::error::Error;
 
@@ -80,6 +83,28 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_head_method_indexable() -> Result<(), Box<dyn Error>> {
+        let got = Method {}["head"].clone();
+
+        assert_eq!(got, reqwest::Method::HEAD, "head should return HEAD method");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_method_indexable() -> Result<(), Box<dyn Error>> {
+        let got = Method {}["options"].clone();
+
+        assert_eq!(
+            got,
+            reqwest::Method::OPTIONS,
+            "options should return OPTIONS method"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_default_method_fallback() -> Result<(), Box<dyn Error>> {
         let got = Method {}["unknown"].clone();
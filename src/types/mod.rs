@@ -1,11 +1,39 @@
 pub mod auth;
+pub mod bench_report;
 pub mod cli;
 pub mod detail;
+pub mod execution;
+pub mod extract;
+pub mod inherit;
+pub mod json_path;
 pub mod method;
 pub mod output;
+pub mod policy;
+pub mod problem;
+pub mod rate;
+pub mod report;
+pub mod retry;
+pub mod save;
+pub mod selection;
+pub mod snapshot;
+pub mod timings;
 
 pub use auth::*;
+pub use bench_report::*;
 pub use cli::*;
 pub use detail::*;
+pub use execution::*;
+pub use extract::*;
+pub use inherit::*;
+pub use json_path::*;
 pub use method::*;
 pub use output::*;
+pub use policy::*;
+pub use problem::*;
+pub use rate::*;
+pub use report::*;
+pub use retry::*;
+pub use save::*;
+pub use selection::*;
+pub use snapshot::*;
+pub use timings::*;
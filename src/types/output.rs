@@ -1,18 +1,61 @@
-#[derive(Debug, cli_table::Table)]
+use cli_table::{format::Justify, CellStruct, Color, Style};
+
+use crate::types::RequestOutcome;
+
+#[derive(Debug, PartialEq, cli_table::Table)]
 pub struct Output {
     #[table(title = "Name")]
     pub name: String,
 
-    // #[table(title = "Method")]
-    // pub method: String,
+    #[table(title = "Method")]
+    pub method: String,
 
-    // #[table(title = "Resource")]
-    // pub resource: String,
     #[table(title = "URL")]
     pub url: String,
 
-    #[table(title = "Status")]
+    #[table(title = "Status", customize_fn = "status_color")]
     pub status: String,
-    // #[table(data = "Data")]
-    // pub data: String,
+
+    #[table(title = "Duration", justify = "Justify::Right")]
+    pub duration: String,
+
+    #[table(title = "Size", justify = "Justify::Right")]
+    pub size: String,
+}
+
+impl From<&RequestOutcome> for Output {
+    fn from(outcome: &RequestOutcome) -> Self {
+        let (status, size) = match &outcome.response {
+            Some(response) => (response.status.to_string(), size(response.size() as usize)),
+            None => (String::from("error"), String::new()),
+        };
+
+        Output {
+            name: outcome.name.clone(),
+            method: outcome.request.method.clone(),
+            url: outcome.request.url.clone(),
+            status,
+            duration: format!("{}ms", outcome.duration.as_millis()),
+            size,
+        }
+    }
+}
+
+fn status_color(cell: CellStruct, status: &str) -> CellStruct {
+    let color = match status.chars().next() {
+        Some('2') => Color::Green,
+        Some('3') => Color::Cyan,
+        Some('4') => Color::Yellow,
+        _ => Color::Red,
+    };
+
+    cell.foreground_color(Some(color))
+}
+
+pub(crate) fn size(bytes: usize) -> String {
+    match bytes {
+        b if b < 1024 => format!("{b} B"),
+        b if b < 1024 * 1024 => format!("{:.1} KiB", b as f64 / 1024.0),
+        b => format!("{:.1} MiB", b as f64 / (1024.0 * 1024.0)),
+    }
 }
@@ -0,0 +1,26 @@
+use crate::types::{Detail, Rate, Retry, Save};
+
+/// How a built request should be executed, as opposed to what is sent.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Policy {
+    pub retry: Option<Retry>,
+    pub rate_limits: Vec<Rate>,
+    pub save: Option<Save>,
+}
+
+impl From<&Detail> for Policy {
+    fn from(detail: &Detail) -> Self {
+        Policy {
+            retry: detail.retry.clone(),
+            rate_limits: detail.rate_limit.iter().cloned().collect(),
+            save: detail
+                .save_to
+                .as_ref()
+                .zip(detail.name.as_ref())
+                .map(|(template, name)| Save {
+                    headers: detail.save_headers.unwrap_or(false),
+                    ..Save::from_template(template, name)
+                }),
+        }
+    }
+}
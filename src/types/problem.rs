@@ -0,0 +1,78 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The request can't be run as written.
+    Error,
+    /// The request can be run, but probably not as intended.
+    Warning,
+}
+
+/// Something wrong with a request definition, found by `validate`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Problem {
+    pub severity: Severity,
+    /// Where the problem is in the tree of requests, e.g. `api.users.get_user`,
+    /// with unnamed requests by their index, e.g. `api[1]`. Empty for
+    /// problems with the whole file.
+    pub path: String,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn error(path: &str, message: String) -> Problem {
+        Problem {
+            severity: Severity::Error,
+            path: path.to_string(),
+            message,
+        }
+    }
+
+    pub fn warning(path: &str, message: String) -> Problem {
+        Problem {
+            severity: Severity::Warning,
+            path: path.to_string(),
+            message,
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+
+        match self.path.is_empty() {
+            true => write!(f, "{severity}: {}", self.message),
+            false => write!(f, "{severity}: {}: {}", self.path, self.message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use crate::Problem;
+
+    #[test]
+    fn test_display_problem() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            Problem::error("api.get_user", String::from("Host is required.")).to_string(),
+            "error: api.get_user: Host is required.",
+        );
+
+        assert_eq!(
+            Problem::warning("", String::from("No requests are defined.")).to_string(),
+            "warning: No requests are defined.",
+            "should leave out an empty path"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr, time::Duration};
+
+/// A maximum request rate, written as `<n>/<unit>`, e.g. `10/s`, `100/m` or `1000/h`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rate {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+impl Rate {
+    /// Minimum time between two consecutive requests.
+    pub fn interval(&self) -> Duration {
+        self.per / self.requests
+    }
+}
+
+impl FromStr for Rate {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("Invalid rate '{value}', expected e.g. '10/s', '100/m' or '1000/h'.");
+
+        let (requests, unit) = value.trim().split_once('/').ok_or_else(invalid)?;
+
+        let requests = requests
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|r| *r > 0)
+            .ok_or_else(invalid)?;
+
+        let per = match unit.trim() {
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(60 * 60),
+            _ => return Err(invalid()),
+        };
+
+        Ok(Rate { requests, per })
+    }
+}
+
+impl TryFrom<String> for Rate {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Rate> for String {
+    fn from(value: Rate) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self.per.as_secs() {
+            3600 => "h",
+            60 => "m",
+            _ => "s",
+        };
+
+        write!(f, "{}/{}", self.requests, unit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Rate;
+    use std::{error::Error, time::Duration};
+
+    #[test]
+    fn test_parse_rate() -> Result<(), Box<dyn Error>> {
+        let got: Rate = "10/s".parse()?;
+
+        assert_eq!(
+            got,
+            Rate {
+                requests: 10,
+                per: Duration::from_secs(1)
+            },
+            "should parse requests per second"
+        );
+        assert_eq!(got.interval(), Duration::from_millis(100));
+
+        let got: Rate = "120/m".parse()?;
+
+        assert_eq!(got.interval(), Duration::from_millis(500));
+        assert_eq!(got.to_string(), "120/m", "should display in same format");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid_rate() -> Result<(), Box<dyn Error>> {
+        assert!("10".parse::<Rate>().is_err(), "should require a unit");
+        assert!("0/s".parse::<Rate>().is_err(), "should reject zero");
+        assert!("ten/s".parse::<Rate>().is_err(), "should require a number");
+        assert!(
+            "10/d".parse::<Rate>().is_err(),
+            "should reject unknown units"
+        );
+
+        Ok(())
+    }
+}
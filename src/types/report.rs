@@ -0,0 +1,43 @@
+use crate::types::{Detail, RequestSnapshot, ResponseSnapshot, Timings};
+use std::time::{Duration, SystemTime};
+
+/// Outcomes of a run, in the order the requests are defined in the config.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunReport {
+    pub outcomes: Vec<RequestOutcome>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestOutcome {
+    pub name: String,
+    /// Names of the groups containing the request, followed by its own name.
+    pub path: Vec<String>,
+    /// The resolved detail the request was built from.
+    pub detail: Detail,
+    pub request: RequestSnapshot,
+    pub response: Option<ResponseSnapshot>,
+    pub error: Option<String>,
+    pub attempts: u32,
+    pub timings: Timings,
+    pub started: SystemTime,
+    pub duration: Duration,
+}
+
+impl RunReport {
+    pub fn get(&self, name: &str) -> Option<&RequestOutcome> {
+        self.outcomes.iter().find(|outcome| outcome.name == name)
+    }
+}
+
+impl RequestOutcome {
+    /// Names of the groups containing the request.
+    pub fn groups(&self) -> &[String] {
+        &self.path[..self.path.len().saturating_sub(1)]
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.response
+            .as_ref()
+            .is_some_and(ResponseSnapshot::is_success)
+    }
+}
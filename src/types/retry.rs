@@ -0,0 +1,162 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Retry {
+    pub attempts: Option<u32>,
+    pub delay: Option<u64>,
+    pub max_delay: Option<u64>,
+    pub jitter: Option<bool>,
+    pub statuses: Option<Vec<u16>>,
+    pub on_connect_error: Option<bool>,
+    pub on_timeout: Option<bool>,
+    pub retry_after: Option<bool>,
+}
+
+impl Default for Retry {
+    fn default() -> Retry {
+        Retry::new()
+    }
+}
+
+impl Retry {
+    pub fn new() -> Retry {
+        Retry {
+            attempts: None,
+            delay: None,
+            max_delay: None,
+            jitter: None,
+            statuses: None,
+            on_connect_error: None,
+            on_timeout: None,
+            retry_after: None,
+        }
+    }
+
+    /// Total number of attempts, including the first one.
+    pub fn max_attempts(&self) -> u32 {
+        self.attempts.unwrap_or(3).max(1)
+    }
+
+    pub fn should_retry_status(&self, status: u16) -> bool {
+        match &self.statuses {
+            Some(statuses) => statuses.contains(&status),
+            None => [429, 502, 503, 504].contains(&status),
+        }
+    }
+
+    pub fn should_retry_error(&self, error: &reqwest::Error) -> bool {
+        (error.is_connect() && self.on_connect_error.unwrap_or(true))
+            || (error.is_timeout() && self.on_timeout.unwrap_or(true))
+    }
+
+    pub fn honours_retry_after(&self) -> bool {
+        self.retry_after.unwrap_or(true)
+    }
+
+    /// Delay to wait after the given (1-based) failed attempt, doubling each
+    /// time from `delay` up to `max_delay` milliseconds. With jitter enabled,
+    /// a random delay between zero and that value is used instead.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let initial = self.delay.unwrap_or(100);
+        let max = self.max_delay.unwrap_or(10_000);
+
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = initial.saturating_mul(1 << exponent).min(max);
+
+        if self.jitter.unwrap_or(true) && delay > 0 {
+            Duration::from_millis(rand::thread_rng().gen_range(0..=delay))
+        } else {
+            Duration::from_millis(delay)
+        }
+    }
+
+    /// Caps a server-provided `Retry-After` delay to `max_delay`.
+    pub fn cap(&self, delay: Duration) -> Duration {
+        delay.min(Duration::from_millis(self.max_delay.unwrap_or(10_000)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Retry;
+    use std::{error::Error, time::Duration};
+
+    #[test]
+    fn test_default_retry_policy() -> Result<(), Box<dyn Error>> {
+        let retry = Retry::default();
+
+        assert_eq!(retry.max_attempts(), 3, "should default to three attempts");
+        assert!(
+            retry.should_retry_status(503),
+            "should retry 503 by default"
+        );
+        assert!(
+            retry.should_retry_status(429),
+            "should retry 429 by default"
+        );
+        assert!(
+            !retry.should_retry_status(500),
+            "should not retry 500 by default"
+        );
+        assert!(retry.honours_retry_after(), "should honour Retry-After");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exponential_backoff_without_jitter() -> Result<(), Box<dyn Error>> {
+        let retry = Retry {
+            delay: Some(100),
+            max_delay: Some(500),
+            jitter: Some(false),
+            ..Retry::new()
+        };
+
+        assert_eq!(retry.backoff(1), Duration::from_millis(100));
+        assert_eq!(retry.backoff(2), Duration::from_millis(200));
+        assert_eq!(retry.backoff(3), Duration::from_millis(400));
+        assert_eq!(
+            retry.backoff(4),
+            Duration::from_millis(500),
+            "should be capped at max delay"
+        );
+        assert_eq!(
+            retry.backoff(64),
+            Duration::from_millis(500),
+            "should not overflow"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_backoff_with_jitter_stays_in_bounds() -> Result<(), Box<dyn Error>> {
+        let retry = Retry {
+            delay: Some(100),
+            jitter: Some(true),
+            ..Retry::new()
+        };
+
+        for _ in 0..100 {
+            assert!(retry.backoff(2) <= Duration::from_millis(200));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_statuses() -> Result<(), Box<dyn Error>> {
+        let retry = Retry {
+            statuses: Some(vec![500]),
+            ..Retry::new()
+        };
+
+        assert!(retry.should_retry_status(500));
+        assert!(!retry.should_retry_status(503));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+/// Where to save a response body, which is streamed to disk as it's received
+/// rather than held in memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Save {
+    pub path: PathBuf,
+    /// Whether to write the status and headers alongside, to `<path>.headers`.
+    pub headers: bool,
+    /// Whether to show progress on stderr for slow downloads.
+    pub progress: bool,
+}
+
+/// A response body saved to disk.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Saved {
+    pub path: PathBuf,
+    /// Size of the body in bytes.
+    pub size: u64,
+}
+
+impl Save {
+    /// Save to a path rendered from a template, in which `{{name}}` is
+    /// replaced with the name of the request.
+    pub fn from_template(template: &str, name: &str) -> Save {
+        Save {
+            path: PathBuf::from(template.replace("{{name}}", name)),
+            headers: false,
+            progress: false,
+        }
+    }
+
+    /// Path the status and headers are written to.
+    pub fn headers_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".headers");
+
+        PathBuf::from(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Save;
+    use std::{error::Error, path::PathBuf};
+
+    #[test]
+    fn test_save_from_template() -> Result<(), Box<dyn Error>> {
+        let got = Save::from_template("./out/{{name}}.json", "get_report");
+
+        assert_eq!(got.path, PathBuf::from("./out/get_report.json"));
+        assert_eq!(
+            got.headers_path(),
+            PathBuf::from("./out/get_report.json.headers")
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+/// Which requests to parse from the config, and how to handle requests with
+/// the same name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Selection {
+    /// Requests or groups to run, along with everything nested in them. All
+    /// requests are run if empty.
+    ///
+    /// Each is a name, found at any depth, or a path from the top of the tree
+    /// separated by `:`, e.g. `users:get_user`. Names may use `*` to match any
+    /// characters, and paths `**` to match any number of groups, e.g.
+    /// `**:delete_*`. Requests can also be selected by their path joined by
+    /// `.`, e.g. `api.users.get_user`, as requests which share a name with
+    /// others are named.
+    pub names: Vec<String>,
+    /// Tags to run requests with any of, or all requests if empty.
+    pub tags: Vec<String>,
+    /// Tags to not run requests with any of, even if otherwise selected.
+    pub exclude_tags: Vec<String>,
+    /// Run only the last of any requests with the same name, rather than
+    /// erroring.
+    pub allow_duplicates: bool,
+}
+
+impl Selection {
+    /// All requests, or those with the given names.
+    pub fn names(names: Vec<String>) -> Selection {
+        Selection {
+            names,
+            ..Selection::default()
+        }
+    }
+
+    /// Whether a request is selected, by its path in the tree, i.e. the names
+    /// of the groups containing it followed by its own name, and the name it's
+    /// run by.
+    pub fn selects(&self, path: &[String], name: &str) -> bool {
+        self.names.is_empty()
+            || self
+                .names
+                .iter()
+                .any(|selector| selects(selector, path, name))
+    }
+
+    /// Whether a request with the given tags is selected.
+    pub fn tagged(&self, tags: &[String]) -> bool {
+        let included = self.tags.is_empty() || self.tags.iter().any(|tag| tags.contains(tag));
+        let excluded = self.exclude_tags.iter().any(|tag| tags.contains(tag));
+
+        included && !excluded
+    }
+}
+
+fn selects(selector: &str, path: &[String], name: &str) -> bool {
+    if selector == name || selector == path.join(".") {
+        return true;
+    }
+
+    let pattern: Vec<&str> = match selector.contains(':') {
+        true => selector.split(':').collect(),
+        false => vec!["**", selector],
+    };
+
+    // Selecting a group selects everything nested in it
+    (1..=path.len()).any(|len| matches(&pattern, &path[..len]))
+}
+
+/// Whether a path matches a pattern exactly, segment by segment.
+fn matches(pattern: &[&str], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| matches(rest, &path[skip..])),
+        Some((segment, rest)) => path
+            .split_first()
+            .is_some_and(|(name, path)| glob(segment, name) && matches(rest, path)),
+    }
+}
+
+/// Whether a value matches a pattern, where `*` matches any characters.
+pub(crate) fn glob(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+
+    let (mut p, mut v) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some(c) if *c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            // Let the last `*` match one more character and try again
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    v = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use crate::Selection;
+
+    fn path(path: &str) -> Vec<String> {
+        path.split(':').map(String::from).collect()
+    }
+
+    #[test]
+    fn test_select_all_by_default() -> Result<(), Box<dyn Error>> {
+        let selection = Selection::default();
+
+        assert!(selection.selects(&path("api:users:get_user"), "get_user"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_by_name() -> Result<(), Box<dyn Error>> {
+        let selection = Selection::names(vec![String::from("users")]);
+
+        assert!(
+            selection.selects(&path("api:users:get_user"), "get_user"),
+            "should select requests nested in a group at any depth"
+        );
+        assert!(!selection.selects(&path("api:posts:get_post"), "get_post"));
+
+        let selection = Selection::names(vec![String::from("api.users.get")]);
+
+        assert!(
+            selection.selects(&path("api:users:get"), "api.users.get"),
+            "should select by the name it's run by"
+        );
+        assert!(
+            selection.selects(&path("api:users:get"), "get"),
+            "should select by its path, even if it isn't named by it"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_by_path() -> Result<(), Box<dyn Error>> {
+        let selection = Selection::names(vec![String::from("api:users:get_user")]);
+
+        assert!(selection.selects(&path("api:users:get_user"), "get_user"));
+        assert!(
+            !selection.selects(&path("admin:api:users:get_user"), "get_user"),
+            "should match paths from the top of the tree"
+        );
+
+        let selection = Selection::names(vec![String::from("api:*")]);
+
+        assert!(selection.selects(&path("api:users:get_user"), "get_user"));
+        assert!(
+            !selection.selects(&path("api"), "api"),
+            "should only select what's nested in the group"
+        );
+
+        let selection = Selection::names(vec![String::from("**:delete_*")]);
+
+        assert!(selection.selects(&path("delete_all"), "delete_all"));
+        assert!(selection.selects(&path("api:users:delete_user"), "delete_user"));
+        assert!(!selection.selects(&path("api:users:get_user"), "get_user"));
+
+        let selection = Selection::names(vec![String::from("api:**:get_*")]);
+
+        assert!(selection.selects(&path("api:get_status"), "get_status"));
+        assert!(selection.selects(&path("api:users:get_user"), "get_user"));
+        assert!(!selection.selects(&path("admin:users:get_user"), "get_user"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_by_tags() -> Result<(), Box<dyn Error>> {
+        let tags = vec![String::from("smoke"), String::from("api")];
+
+        assert!(Selection::default().tagged(&tags));
+        assert!(
+            Selection::default().tagged(&[]),
+            "should select untagged requests"
+        );
+
+        let selection = Selection {
+            tags: vec![String::from("smoke")],
+            exclude_tags: vec![String::from("slow")],
+            ..Selection::default()
+        };
+
+        assert!(selection.tagged(&tags));
+        assert!(!selection.tagged(&[]));
+        assert!(!selection.tagged(&[String::from("smoke"), String::from("slow")]));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use crate::types::Saved;
+
+/// The request as built, before the HTTP client adds default headers such as
+/// `User-Agent` when sending it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestSnapshot {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResponseSnapshot {
+    pub status: u16,
+    /// HTTP version of the response, e.g. `HTTP/1.1`.
+    pub version: String,
+    /// Final URL of the response, after following any redirects.
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// Body of the response, empty if it was saved to disk instead.
+    pub body: Vec<u8>,
+    pub saved: Option<Saved>,
+}
+
+impl From<&reqwest::Request> for RequestSnapshot {
+    fn from(request: &reqwest::Request) -> Self {
+        RequestSnapshot {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers: headers(request.headers()),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|body| body.to_vec()),
+        }
+    }
+}
+
+impl RequestSnapshot {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        header(&self.headers, name)
+    }
+}
+
+impl ResponseSnapshot {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        header(&self.headers, name)
+    }
+
+    /// Size of the body in bytes, whether held in memory or saved to disk.
+    pub fn size(&self) -> u64 {
+        match &self.saved {
+            Some(saved) => saved.size,
+            None => self.body.len() as u64,
+        }
+    }
+
+    /// Whether the status is a success, i.e. 2xx, or a redirect which wasn't
+    /// followed, i.e. 3xx.
+    pub fn is_success(&self) -> bool {
+        (200..400).contains(&self.status)
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).to_string()
+    }
+}
+
+pub(crate) fn headers(headers: &reqwest::header::HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            )
+        })
+        .collect()
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
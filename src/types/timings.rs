@@ -0,0 +1,17 @@
+use std::time::Duration;
+
+/// Timing phases of the final attempt of a request.
+///
+/// Connection and TLS handshake phases aren't exposed by the HTTP client, so
+/// they're included in `ttfb`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Timings {
+    /// Time taken to resolve the host, if a new connection needed a lookup.
+    /// Approximate with parallel requests to the same host.
+    pub dns: Option<Duration>,
+    /// Time from sending the request until the response headers were received.
+    pub ttfb: Duration,
+    /// Time taken to receive the response body.
+    pub download: Duration,
+    pub total: Duration,
+}
@@ -0,0 +1,204 @@
+use std::{
+    env, error::Error, fs, net::TcpListener, path::PathBuf, sync::Once, thread, time::Duration,
+};
+
+use actix_web::{web, App, HttpResponse, HttpServer};
+use corkscrew::{bench, Bench, OutputFormat, Settings};
+
+#[tokio::test]
+async fn test_bench_repeat() -> Result<(), Box<dyn Error>> {
+    let settings = Settings {
+        config_path: PathBuf::from("tests/e2e-config.yml"),
+        parallel: 0,
+        tags: vec![],
+        exclude_tags: vec![],
+        allow_duplicates: false,
+        har: None,
+        save_headers: false,
+        output_dir: None,
+        extract: None,
+        show_secrets: false,
+        verbose: 0,
+        reports: vec![],
+        output: OutputFormat::Table,
+        no_color: false,
+        rate: None,
+        command: None,
+        request_names: vec![],
+    };
+
+    let options = Bench {
+        request_names: vec![String::from("http_get"), String::from("http_delete")],
+        repeat: Some(20),
+        duration: None,
+        concurrency: 4,
+    };
+
+    start_server(7878).await;
+
+    let reports = bench::go(settings, options).await?;
+
+    assert_eq!(2, reports.len(), "should report on each request");
+
+    let get = reports
+        .iter()
+        .find(|r| r.name == "http_get")
+        .expect("should have report for get");
+
+    assert_eq!(20, get.requests, "should repeat the request");
+    assert_eq!(0, get.errors, "should not have errors");
+    assert!(get.p50 <= get.p99, "percentiles should be ordered");
+    assert!(get.p99 <= get.max, "max should be the slowest");
+
+    let delete = reports
+        .iter()
+        .find(|r| r.name == "http_delete")
+        .expect("should have report for delete");
+
+    assert_eq!(20, delete.errors, "non-success responses should be errors");
+    assert_eq!(1.0, delete.error_rate());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bench_duration() -> Result<(), Box<dyn Error>> {
+    let settings = Settings {
+        config_path: PathBuf::from("tests/e2e-config.yml"),
+        parallel: 0,
+        tags: vec![],
+        exclude_tags: vec![],
+        allow_duplicates: false,
+        har: None,
+        save_headers: false,
+        output_dir: None,
+        extract: None,
+        show_secrets: false,
+        verbose: 0,
+        reports: vec![],
+        output: OutputFormat::Table,
+        no_color: false,
+        rate: Some("20/s".parse()?),
+        command: None,
+        request_names: vec![],
+    };
+
+    let options = Bench {
+        request_names: vec![String::from("http_get")],
+        repeat: None,
+        duration: Some(Duration::from_millis(500)),
+        concurrency: 2,
+    };
+
+    start_server(7878).await;
+
+    let reports = bench::go(settings, options).await?;
+
+    let get = reports.first().expect("should have report");
+
+    assert!(
+        get.elapsed >= Duration::from_millis(500),
+        "should run for the duration"
+    );
+    assert!(
+        get.requests <= 12,
+        "should be limited by the rate, got {}",
+        get.requests
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bench_does_not_save() -> Result<(), Box<dyn Error>> {
+    let dir = env::temp_dir().join("corkscrew_test_bench_does_not_save");
+
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("out"))?;
+
+    let config_path = dir.join("requests.yml");
+
+    fs::write(
+        &config_path,
+        "
+- name: http_get
+  host: localhost
+  port: 7878
+  resource: /test_http_methods
+  save_to: '{{name}}.txt'
+",
+    )?;
+
+    let settings = Settings {
+        config_path,
+        parallel: 0,
+        tags: vec![],
+        exclude_tags: vec![],
+        allow_duplicates: false,
+        har: None,
+        save_headers: true,
+        output_dir: Some(dir.join("out")),
+        extract: None,
+        show_secrets: false,
+        verbose: 0,
+        reports: vec![],
+        output: OutputFormat::Table,
+        no_color: false,
+        rate: None,
+        command: None,
+        request_names: vec![],
+    };
+
+    let options = Bench {
+        request_names: vec![],
+        repeat: Some(5),
+        duration: None,
+        concurrency: 2,
+    };
+
+    start_server(7878).await;
+
+    let reports = bench::go(settings, options).await?;
+
+    assert_eq!(5, reports[0].requests, "should repeat the request");
+    assert_eq!(
+        0,
+        fs::read_dir(dir.join("out"))?.count(),
+        "should not save responses"
+    );
+
+    Ok(())
+}
+
+static SERVER: Once = Once::new();
+
+async fn start_server(port: u16) {
+    SERVER.call_once(|| {
+        let listener =
+            TcpListener::bind(format!("localhost:{}", port)).expect("unable to bind to port");
+
+        thread::spawn(move || {
+            tokio::runtime::Runtime::new()
+                .expect("unable to start runtime")
+                .block_on(async move {
+                    HttpServer::new(move || {
+                        App::new().service(
+                            web::resource("/test_http_methods")
+                                .route(
+                                    web::get().to(|| async { HttpResponse::Ok().body("get_ok") }),
+                                )
+                                .route(
+                                    web::delete().to(|| async {
+                                        HttpResponse::InternalServerError().finish()
+                                    }),
+                                ),
+                        )
+                    })
+                    .listen(listener)
+                    .expect("unable to start server")
+                    .run()
+                    .await
+                })
+        });
+    });
+}
@@ -10,6 +10,15 @@ mod test {
         let details = vec![Detail {
             name: Some(String::from("test_required_props")),
             requests: None,
+            inherit: None,
+            unset: None,
+            tags: None,
+            variables: None,
+            save_headers: None,
+            save_to: None,
+            output: None,
+            rate_limit: None,
+            retry: None,
             host: Some(String::from("localhost")),
             scheme: None,
             port: None,
@@ -57,6 +66,15 @@ mod test {
                 name: Some(String::from("test_form_post")),
                 host: Some(String::from("localhost")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
                 scheme: Some(String::from("https")),
                 port: Some(7878),
                 timeout: Some(30),
@@ -92,6 +110,15 @@ mod test {
                 name: Some(String::from("test_json_post")),
                 host: Some(String::from("localhost")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
                 scheme: None,
                 port: None,
                 timeout: None,
@@ -201,6 +228,46 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_build_auth_with_headers() -> Result<(), Box<dyn Error>> {
+        let details = vec![Detail {
+            name: Some(String::from("test_auth_with_headers")),
+            host: Some(String::from("localhost")),
+            resource: Some(String::from("/test_auth_with_headers")),
+            auth: Some(AuthType::Bearer {
+                token: String::from("abc"),
+            }),
+            headers: Some(HashMap::from([(
+                String::from("x-custom"),
+                String::from("value"),
+            )])),
+            ..Detail::new()
+        }];
+
+        let requests = builder::build(details)?;
+
+        let r1 = requests
+            .get("test_auth_with_headers")
+            .expect("should have request");
+
+        assert_eq!(
+            Some("Bearer abc"),
+            r1.headers()
+                .get("authorization")
+                .and_then(|value| value.to_str().ok()),
+            "should keep the auth header"
+        );
+        assert_eq!(
+            Some("value"),
+            r1.headers()
+                .get("x-custom")
+                .and_then(|value| value.to_str().ok()),
+            "should add custom headers"
+        );
+
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn test_panic_on_missing_name() {
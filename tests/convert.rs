@@ -0,0 +1,946 @@
+#[cfg(test)]
+mod test {
+    use corkscrew::{
+        builder,
+        convert::{curl, har, http, openapi, postman},
+        parser, AuthType, Detail, Import, ImportFormat,
+    };
+    use std::{collections::HashMap, error::Error};
+
+    fn curl(detail: Detail) -> Result<String, Box<dyn Error>> {
+        let requests = builder::build(vec![detail.clone()])?;
+
+        let request = requests
+            .get(detail.name.as_deref().unwrap_or_default())
+            .expect("should build request");
+
+        Ok(curl::curl(request, &detail))
+    }
+
+    fn http(detail: Detail) -> Result<String, Box<dyn Error>> {
+        let requests = builder::build(vec![detail.clone()])?;
+
+        let request = requests
+            .get(detail.name.as_deref().unwrap_or_default())
+            .expect("should build request");
+
+        Ok(http::http(request, &detail))
+    }
+
+    fn detail() -> Detail {
+        Detail {
+            name: Some(String::from("test_request")),
+            host: Some(String::from("localhost")),
+            port: Some(8080),
+            resource: Some(String::from("/api/users")),
+            ..Detail::new()
+        }
+    }
+
+    #[test]
+    fn test_curl_get() -> Result<(), Box<dyn Error>> {
+        let got = curl(Detail {
+            params: Some(HashMap::from([(String::from("q"), String::from("it's"))])),
+            hash: Some(String::from("top")),
+            ..detail()
+        })?;
+
+        assert_eq!(
+            got, r"curl 'http://localhost:8080/api/users?q=it%27s'",
+            "should leave out method, fragment and default body"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_curl_json_body() -> Result<(), Box<dyn Error>> {
+        let got = curl(Detail {
+            method: Some(String::from("post")),
+            body: Some(serde_json::json!({ "name": "O'Brien" })),
+            headers: Some(HashMap::from([(
+                String::from("X-Request-Id"),
+                String::from("abc"),
+            )])),
+            auth: Some(AuthType::Bearer {
+                token: String::from("t0k3n"),
+            }),
+            timeout: Some(5),
+            ..detail()
+        })?;
+
+        assert_eq!(
+            got,
+            r#"curl -X POST 'http://localhost:8080/api/users' -H 'authorization: Bearer t0k3n' -H 'content-type: application/json' -H 'x-request-id: abc' --data-raw '{"name":"O'\''Brien"}' --max-time 5"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_curl_form_with_basic_auth() -> Result<(), Box<dyn Error>> {
+        let got = curl(Detail {
+            method: Some(String::from("put")),
+            form: Some(HashMap::from([(String::from("name"), String::from("a b"))])),
+            auth: Some(AuthType::Basic {
+                username: String::from("user"),
+                password: String::from("p4ss"),
+            }),
+            ..detail()
+        })?;
+
+        assert_eq!(
+            got,
+            "curl -X PUT -u 'user:p4ss' 'http://localhost:8080/api/users' -H 'content-type: application/x-www-form-urlencoded' --data-raw 'name=a+b'"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_curl_head() -> Result<(), Box<dyn Error>> {
+        let got = curl(Detail {
+            method: Some(String::from("head")),
+            ..detail()
+        })?;
+
+        assert_eq!(got, "curl -I 'http://localhost:8080/api/users'");
+
+        let imported = curl::import(&got)?;
+
+        assert_eq!(
+            imported[0].method.as_deref(),
+            Some("head"),
+            "should import as a HEAD request"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_curl() -> Result<(), Box<dyn Error>> {
+        let input = r#"
+curl 'https://api.example.com/v1/users?page=2' \
+  -H 'accept: application/json' \
+  -H 'authorization: Bearer abc.def' \
+  -H 'content-type: application/json' \
+  -H 'content-length: 31' \
+  --data-raw $'{"name":"O\'Brien","tags":["a"]}' \
+  --compressed
+
+# comments and other commands are ignored
+echo done
+curl -sSL -XPUT "http://localhost:8080/login" -d 'user=ann' --data 'pass=x%20y' -m 2.5
+curl -G https://api.example.com/v1/users/ -d q=ann -H "Authorization: Basic YWRtaW46c2VjcmV0"
+"#;
+
+        let got = curl::import(input)?;
+
+        let want = vec![
+            Detail {
+                name: Some(String::from("post_v1_users")),
+                scheme: Some(String::from("https")),
+                host: Some(String::from("api.example.com")),
+                resource: Some(String::from("/v1/users")),
+                method: Some(String::from("post")),
+                params: Some(HashMap::from([(String::from("page"), String::from("2"))])),
+                headers: Some(HashMap::from([(
+                    String::from("accept"),
+                    String::from("application/json"),
+                )])),
+                auth: Some(AuthType::Bearer {
+                    token: String::from("abc.def"),
+                }),
+                body: Some(serde_json::json!({ "name": "O'Brien", "tags": ["a"] })),
+                ..Detail::new()
+            },
+            Detail {
+                name: Some(String::from("put_login")),
+                host: Some(String::from("localhost")),
+                port: Some(8080),
+                timeout: Some(3),
+                resource: Some(String::from("/login")),
+                method: Some(String::from("put")),
+                form: Some(HashMap::from([
+                    (String::from("user"), String::from("ann")),
+                    (String::from("pass"), String::from("x y")),
+                ])),
+                ..Detail::new()
+            },
+            Detail {
+                name: Some(String::from("get_v1_users")),
+                scheme: Some(String::from("https")),
+                host: Some(String::from("api.example.com")),
+                resource: Some(String::from("/v1/users/")),
+                params: Some(HashMap::from([(String::from("q"), String::from("ann"))])),
+                auth: Some(AuthType::Basic {
+                    username: String::from("admin"),
+                    password: String::from("secret"),
+                }),
+                ..Detail::new()
+            },
+        ];
+
+        assert_eq!(got, want);
+
+        let got = curl::import(
+            "curl localhost -H 'Content-Type: Application/X-WWW-Form-Urlencoded; charset=UTF-8' -d a=1",
+        )?;
+
+        assert_eq!(
+            got[0].form,
+            Some(HashMap::from([(String::from("a"), String::from("1"))])),
+            "should match the content type without its parameters"
+        );
+
+        // Credentials ending in 1, 2 and 3 bytes, with and without padding
+        for (credentials, username, password) in [
+            ("YWI6Yw==", "ab", "c"),
+            ("YWI6Yw", "ab", "c"),
+            ("YWI6Y2Q=", "ab", "cd"),
+            ("YTpi", "a", "b"),
+        ] {
+            let got = curl::import(&format!(
+                "curl localhost -H 'Authorization: Basic {credentials}'"
+            ))?;
+
+            assert_eq!(
+                got[0].auth,
+                Some(AuthType::Basic {
+                    username: String::from(username),
+                    password: String::from(password),
+                }),
+                "should decode basic credentials '{credentials}'"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_curl_errors() -> Result<(), Box<dyn Error>> {
+        assert!(
+            curl::import("curl 'http://localhost").is_err(),
+            "unclosed quote"
+        );
+        assert!(curl::import("curl -X POST").is_err(), "missing URL");
+        assert!(
+            curl::import("curl localhost -F file=@a.txt").is_err(),
+            "multipart form"
+        );
+        assert!(
+            curl::import("curl localhost -d @body.json").is_err(),
+            "body read from a file"
+        );
+        assert!(
+            curl::import("curl localhost --data-urlencode name@a.txt").is_err(),
+            "urlencoded body read from a file"
+        );
+        assert!(
+            curl::import("curl -T a.txt localhost").is_err(),
+            "uploaded file"
+        );
+        assert!(
+            curl::import("curl localhost -H 'content-type: application/json' -d '{'").is_err(),
+            "invalid JSON"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_postman() -> Result<(), Box<dyn Error>> {
+        let input = r#"{
+  "info": {
+    "name": "Petstore API",
+    "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+  },
+  "auth": { "type": "bearer", "bearer": [{ "key": "token", "value": "{{token}}" }] },
+  "variable": [
+    { "key": "base", "value": "https://api.example.com" },
+    { "key": "token", "value": "abc123" }
+  ],
+  "item": [
+    {
+      "name": "Users",
+      "item": [
+        {
+          "name": "List users",
+          "request": {
+            "method": "GET",
+            "header": [
+              { "key": "Accept", "value": "application/json" },
+              { "key": "X-Debug", "value": "1", "disabled": true }
+            ],
+            "url": { "raw": "{{base}}/users?page=1", "host": ["{{base}}"], "path": ["users"] }
+          }
+        },
+        {
+          "name": "Create user",
+          "event": [{ "listen": "test", "script": { "exec": ["pm.test('ok')"] } }],
+          "request": {
+            "method": "POST",
+            "header": [{ "key": "Content-Type", "value": "application/json" }],
+            "body": { "mode": "raw", "raw": "{\"name\": \"{{name}}\"}" },
+            "url": "{{base}}/users"
+          }
+        }
+      ]
+    },
+    {
+      "name": "Login",
+      "request": {
+        "method": "POST",
+        "auth": { "type": "basic", "basic": [
+          { "key": "username", "value": "ann" },
+          { "key": "password", "value": "secret" }
+        ] },
+        "body": { "mode": "urlencoded", "urlencoded": [{ "key": "remember", "value": "true" }] },
+        "url": { "protocol": "http", "host": ["localhost"], "port": "8080", "path": ["login"] }
+      }
+    },
+    {
+      "name": "Upload",
+      "request": { "method": "PUT", "body": { "mode": "file" }, "url": "{{base}}/upload" }
+    }
+  ]
+}"#;
+
+        let (got, warnings) = postman::import(input)?;
+
+        let api = |name: &str, method: Option<&str>, resource: &str| Detail {
+            name: Some(String::from(name)),
+            scheme: Some(String::from("https")),
+            host: Some(String::from("api.example.com")),
+            resource: Some(String::from(resource)),
+            method: method.map(String::from),
+            ..Detail::new()
+        };
+
+        let want = vec![Detail {
+            name: Some(String::from("petstore_api")),
+            variables: Some(HashMap::from([(String::from("name"), String::new())])),
+            auth: Some(AuthType::Bearer {
+                token: String::from("abc123"),
+            }),
+            requests: Some(vec![
+                Detail {
+                    name: Some(String::from("users")),
+                    requests: Some(vec![
+                        Detail {
+                            params: Some(HashMap::from([(
+                                String::from("page"),
+                                String::from("1"),
+                            )])),
+                            headers: Some(HashMap::from([(
+                                String::from("Accept"),
+                                String::from("application/json"),
+                            )])),
+                            ..api("list_users", None, "/users")
+                        },
+                        Detail {
+                            body: Some(serde_json::json!({ "name": "{{name}}" })),
+                            ..api("create_user", Some("post"), "/users")
+                        },
+                    ]),
+                    ..Detail::new()
+                },
+                Detail {
+                    name: Some(String::from("login")),
+                    host: Some(String::from("localhost")),
+                    port: Some(8080),
+                    resource: Some(String::from("/login")),
+                    method: Some(String::from("post")),
+                    auth: Some(AuthType::Basic {
+                        username: String::from("ann"),
+                        password: String::from("secret"),
+                    }),
+                    form: Some(HashMap::from([(
+                        String::from("remember"),
+                        String::from("true"),
+                    )])),
+                    ..Detail::new()
+                },
+                api("upload", Some("put"), "/upload"),
+            ]),
+            ..Detail::new()
+        }];
+
+        assert_eq!(got, want);
+
+        assert_eq!(
+            warnings,
+            vec![
+                "'Petstore API / Users / Create user' has a test script, which is not supported.",
+                "Variable '{{name}}' is not defined in the collection, so it's empty to be filled in.",
+                "'Petstore API / Upload' has a file body, but only JSON, urlencoded and GraphQL bodies are supported.",
+            ],
+            "should warn about anything not converted"
+        );
+
+        assert_eq!(
+            parser::parse(&serde_yaml::to_string(&got)?, vec![])[1].body,
+            Some(serde_json::json!({ "name": "" })),
+            "should run with undefined variables left empty"
+        );
+
+        assert!(
+            postman::import(r#"{"info": {"name": "a", "schema": "v1.0.0"}}"#).is_err(),
+            "unsupported schema"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_postman_variables_in_url() -> Result<(), Box<dyn Error>> {
+        let input = r#"{
+  "info": { "name": "API" },
+  "item": [{ "name": "Get user", "request": { "url": "{{baseUrl}}/users/{{id}}?fields={{Fields}}" } }]
+}"#;
+
+        let (got, _) = postman::import(input)?;
+        let request = &got[0].requests.as_ref().unwrap()[0];
+
+        assert_eq!(
+            (request.host.as_deref(), request.resource.as_deref()),
+            (Some("{{baseUrl}}"), Some("/users/{{id}}")),
+            "should leave variables as they are"
+        );
+        assert_eq!(
+            request.params,
+            Some(HashMap::from([(
+                String::from("fields"),
+                String::from("{{Fields}}")
+            )]))
+        );
+
+        let mut group = got[0].clone();
+
+        group.variables = Some(HashMap::from([
+            (String::from("baseUrl"), String::from("api.example.com")),
+            (String::from("id"), String::from("7")),
+            (String::from("Fields"), String::from("name")),
+        ]));
+
+        let parsed = &parser::parse(&serde_yaml::to_string(&vec![group])?, vec![])[0];
+
+        assert_eq!(
+            (parsed.host.as_deref(), parsed.resource.as_deref()),
+            (Some("api.example.com"), Some("/users/7"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_postman_api_keys() -> Result<(), Box<dyn Error>> {
+        let input = r#"{
+  "info": { "name": "API" },
+  "auth": { "type": "apikey", "apikey": [{ "key": "key", "value": "X-Key" }, { "key": "value", "value": "abc" }] },
+  "item": [
+    { "name": "Users", "request": { "url": "https://example.com/users" } },
+    {
+      "name": "Public",
+      "request": { "auth": { "type": "noauth" }, "url": "https://example.com/public" }
+    },
+    {
+      "name": "Search",
+      "request": {
+        "auth": { "type": "apikey", "apikey": [
+          { "key": "in", "value": "query" },
+          { "key": "key", "value": "key" },
+          { "key": "value", "value": "def" }
+        ] },
+        "url": "https://example.com/search"
+      }
+    }
+  ]
+}"#;
+
+        let (got, warnings) = postman::import(input)?;
+
+        assert!(warnings.is_empty());
+        assert_eq!(
+            got[0].headers,
+            Some(HashMap::from([(
+                String::from("X-Key"),
+                String::from("abc")
+            )])),
+            "should add API keys where they're set"
+        );
+
+        let parsed = parser::parse(&serde_yaml::to_string(&got)?, vec![]);
+
+        let headers = |index: usize| {
+            parsed[index]
+                .headers
+                .as_ref()
+                .and_then(|headers| headers.get("X-Key"))
+                .cloned()
+        };
+
+        assert_eq!(headers(0), Some(String::from("abc")));
+        assert_eq!(headers(1), None, "should unset API keys for other auth");
+        assert_eq!(headers(2), None);
+        assert_eq!(
+            parsed[2].params,
+            Some(HashMap::from([(String::from("key"), String::from("def"))]))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_openapi() -> Result<(), Box<dyn Error>> {
+        let input = r#"
+openapi: 3.0.3
+info:
+  title: Pet Store
+  version: 1.0.0
+servers:
+  - url: https://{env}.example.com:8443/v1/
+    variables:
+      env:
+        default: api
+security:
+  - bearer: []
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      tags: [pets]
+      parameters:
+        - { name: limit, in: query, example: 20 }
+        - { name: offset, in: query, schema: { type: integer } }
+        - $ref: '#/components/parameters/Tenant'
+    post:
+      operationId: createPet
+      tags: [pets]
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: '#/components/schemas/Pet' }
+  /pets/{petId}:
+    parameters:
+      - { name: petId, in: path, required: true, schema: { type: integer } }
+    delete:
+      tags: [pets]
+      security:
+        - basic: []
+  /upload/{fileId}:
+    put:
+      requestBody:
+        content:
+          multipart/form-data: {}
+      responses:
+        200: { description: ok }
+components:
+  parameters:
+    Tenant:
+      { name: X-Tenant, in: header, required: true, schema: { enum: [acme, globex] } }
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name: { type: string, example: Rex }
+        born: { type: string, format: date }
+        parent: { $ref: '#/components/schemas/Pet' }
+        tags: { type: array, items: { type: string } }
+  securitySchemes:
+    bearer: { type: http, scheme: bearer }
+    basic: { type: http, scheme: basic }
+"#;
+
+        let (got, warnings) = openapi::import(input)?;
+
+        let want = vec![Detail {
+            name: Some(String::from("pet_store")),
+            scheme: Some(String::from("https")),
+            host: Some(String::from("api.example.com")),
+            port: Some(8443),
+            variables: Some(HashMap::from([
+                (String::from("token"), String::new()),
+                (String::from("username"), String::new()),
+                (String::from("password"), String::new()),
+            ])),
+            auth: Some(AuthType::Bearer {
+                token: String::from("{{token}}"),
+            }),
+            requests: Some(vec![
+                Detail {
+                    name: Some(String::from("pets")),
+                    requests: Some(vec![
+                        Detail {
+                            name: Some(String::from("list_pets")),
+                            resource: Some(String::from("/v1/pets")),
+                            params: Some(HashMap::from([(
+                                String::from("limit"),
+                                String::from("20"),
+                            )])),
+                            headers: Some(HashMap::from([(
+                                String::from("X-Tenant"),
+                                String::from("acme"),
+                            )])),
+                            ..Detail::new()
+                        },
+                        Detail {
+                            name: Some(String::from("create_pet")),
+                            resource: Some(String::from("/v1/pets")),
+                            method: Some(String::from("post")),
+                            body: Some(serde_json::json!({
+                                "name": "Rex",
+                                "born": "2024-01-01",
+                                "tags": ["string"],
+                            })),
+                            ..Detail::new()
+                        },
+                        Detail {
+                            name: Some(String::from("delete_pets_petid")),
+                            resource: Some(String::from("/v1/pets/{{petId}}")),
+                            method: Some(String::from("delete")),
+                            variables: Some(HashMap::from([(
+                                String::from("petId"),
+                                String::from("1"),
+                            )])),
+                            auth: Some(AuthType::Basic {
+                                username: String::from("{{username}}"),
+                                password: String::from("{{password}}"),
+                            }),
+                            ..Detail::new()
+                        },
+                    ]),
+                    ..Detail::new()
+                },
+                Detail {
+                    name: Some(String::from("put_upload_fileid")),
+                    resource: Some(String::from("/v1/upload/{{fileId}}")),
+                    method: Some(String::from("put")),
+                    variables: Some(HashMap::from([(String::from("fileId"), String::new())])),
+                    ..Detail::new()
+                },
+            ]),
+            ..Detail::new()
+        }];
+
+        assert_eq!(got, want);
+
+        assert_eq!(
+            warnings,
+            vec![
+                "'put_upload_fileid' has no 'fileId' path parameter, so it's an empty variable to be filled in.",
+                "'put_upload_fileid' has a multipart/form-data body, but only JSON and form data bodies are supported.",
+            ]
+        );
+
+        assert_eq!(
+            parser::parse(&serde_yaml::to_string(&got)?, vec![])[3].resource,
+            Some(String::from("/v1/upload/")),
+            "should run with undefined path parameters left empty"
+        );
+
+        assert!(
+            openapi::import("swagger: '2.0'").is_err(),
+            "unsupported version"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_http_export() -> Result<(), Box<dyn Error>> {
+        let got = http(Detail {
+            method: Some(String::from("post")),
+            body: Some(serde_json::json!({ "name": "Ann" })),
+            auth: Some(AuthType::Basic {
+                username: String::from("user"),
+                password: String::from("p4ss"),
+            }),
+            timeout: Some(5),
+            ..detail()
+        })?;
+
+        let want = r#"### test_request
+# @name test_request
+# @timeout 5
+POST http://localhost:8080/api/users
+authorization: Basic dXNlcjpwNHNz
+content-type: application/json
+
+{
+  "name": "Ann"
+}"#;
+
+        assert_eq!(got, want);
+
+        let got = http(detail())?;
+
+        assert_eq!(
+            got, "### test_request\n# @name test_request\nGET http://localhost:8080/api/users",
+            "should leave out default body"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_http() -> Result<(), Box<dyn Error>> {
+        let input = r#"
+@host = localhost:8080
+@base = http://{{host}}/api
+
+### List users
+GET {{base}}/users?page=2
+    &size=10 HTTP/1.1
+Accept: application/json
+Authorization: Bearer {{token}}
+
+###
+@age = 30
+# @name create_user
+// @timeout 3
+POST https://api.example.com/users
+Content-Type: application/json
+
+{"name": "Ann", "age": {{age}}}
+
+> {% client.global.set("id", response.body.id) %}
+
+### Log in
+POST /login
+Host: example.com
+Content-Type: application/x-www-form-urlencoded
+Authorization: Basic ann secret
+
+user=ann
+&remember=true
+"#;
+
+        let (got, warnings) = http::import(input)?;
+
+        let want = vec![Detail {
+            variables: Some(HashMap::from([
+                (String::from("host"), String::from("localhost:8080")),
+                (String::from("base"), String::from("http://{{host}}/api")),
+                (String::from("age"), String::from("30")),
+            ])),
+            requests: Some(vec![
+                Detail {
+                    name: Some(String::from("list_users")),
+                    host: Some(String::from("{{host}}")),
+                    resource: Some(String::from("/api/users")),
+                    params: Some(HashMap::from([
+                        (String::from("page"), String::from("2")),
+                        (String::from("size"), String::from("10")),
+                    ])),
+                    headers: Some(HashMap::from([(
+                        String::from("Accept"),
+                        String::from("application/json"),
+                    )])),
+                    auth: Some(AuthType::Bearer {
+                        token: String::from("{{token}}"),
+                    }),
+                    ..Detail::new()
+                },
+                Detail {
+                    name: Some(String::from("create_user")),
+                    scheme: Some(String::from("https")),
+                    host: Some(String::from("api.example.com")),
+                    resource: Some(String::from("/users")),
+                    method: Some(String::from("post")),
+                    timeout: Some(3),
+                    body: Some(serde_json::json!({ "name": "Ann", "age": 30 })),
+                    ..Detail::new()
+                },
+                Detail {
+                    name: Some(String::from("log_in")),
+                    host: Some(String::from("example.com")),
+                    resource: Some(String::from("/login")),
+                    method: Some(String::from("post")),
+                    auth: Some(AuthType::Basic {
+                        username: String::from("ann"),
+                        password: String::from("secret"),
+                    }),
+                    form: Some(HashMap::from([
+                        (String::from("user"), String::from("ann")),
+                        (String::from("remember"), String::from("true")),
+                    ])),
+                    ..Detail::new()
+                },
+            ]),
+            ..Detail::new()
+        }];
+
+        assert_eq!(got, want);
+
+        assert_eq!(
+            warnings,
+            vec![
+                "'create_user' has a response handler or redirect, which is not supported.",
+                "Variable 'token' is not defined in the file, so must be added.",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_har() -> Result<(), Box<dyn Error>> {
+        let input = r#"{
+  "log": {
+    "version": "1.2",
+    "entries": [
+      {
+        "request": {
+          "method": "GET",
+          "url": "https://api.example.com/users?page=2",
+          "headers": [
+            { "name": ":authority", "value": "api.example.com" },
+            { "name": "Accept", "value": "application/json" },
+            { "name": "Accept-Encoding", "value": "gzip, br" },
+            { "name": "Cookie", "value": "session=abc" },
+            { "name": "Authorization", "value": "Bearer abc123" }
+          ]
+        }
+      },
+      {
+        "request": {
+          "method": "GET",
+          "url": "https://api.example.com/users?page=2",
+          "headers": [
+            { "name": "Accept", "value": "application/json" },
+            { "name": "Cookie", "value": "session=abc" },
+            { "name": "Authorization", "value": "Bearer abc123" }
+          ]
+        }
+      },
+      {
+        "request": {
+          "method": "OPTIONS",
+          "url": "https://api.example.com/users",
+          "headers": [
+            { "name": "Access-Control-Request-Method", "value": "POST" },
+            { "name": "Origin", "value": "https://example.com" }
+          ]
+        }
+      },
+      {
+        "request": {
+          "method": "POST",
+          "url": "https://api.example.com/users",
+          "headers": [{ "name": "Content-Type", "value": "application/json" }],
+          "postData": { "mimeType": "application/json", "text": "{\"name\":\"Ann\"}" }
+        }
+      },
+      {
+        "request": {
+          "method": "POST",
+          "url": "http://localhost:3000/login",
+          "headers": [],
+          "postData": {
+            "mimeType": "application/x-www-form-urlencoded",
+            "params": [{ "name": "user", "value": "ann" }]
+          }
+        }
+      },
+      {
+        "request": {
+          "method": "GET",
+          "url": "https://cdn.example.com/app.js",
+          "headers": []
+        }
+      },
+      {
+        "request": { "method": "GET", "url": "data:image/png;base64,AAAA" }
+      }
+    ]
+  }
+}"#;
+
+        let import = Import {
+            format: ImportFormat::Har,
+            path: None,
+            into: None,
+            group: None,
+            hosts: vec![String::from("api.*"), String::from("localhost:3000")],
+            methods: vec![],
+            urls: vec![],
+            strip_cookies: true,
+            strip_auth: false,
+        };
+
+        let (got, warnings) = har::import(input, &import)?;
+
+        assert_eq!(
+            got,
+            vec![
+                Detail {
+                    name: Some(String::from("api_example_com")),
+                    scheme: Some(String::from("https")),
+                    host: Some(String::from("api.example.com")),
+                    requests: Some(vec![
+                        Detail {
+                            name: Some(String::from("get_users")),
+                            resource: Some(String::from("/users")),
+                            params: Some(HashMap::from([(
+                                String::from("page"),
+                                String::from("2")
+                            )])),
+                            headers: Some(HashMap::from([(
+                                String::from("accept"),
+                                String::from("application/json")
+                            )])),
+                            auth: Some(AuthType::Bearer {
+                                token: String::from("abc123")
+                            }),
+                            ..Detail::new()
+                        },
+                        Detail {
+                            name: Some(String::from("post_users")),
+                            method: Some(String::from("post")),
+                            resource: Some(String::from("/users")),
+                            body: Some(serde_json::json!({ "name": "Ann" })),
+                            ..Detail::new()
+                        },
+                    ]),
+                    ..Detail::new()
+                },
+                Detail {
+                    name: Some(String::from("localhost")),
+                    host: Some(String::from("localhost")),
+                    port: Some(3000),
+                    requests: Some(vec![Detail {
+                        name: Some(String::from("post_login")),
+                        method: Some(String::from("post")),
+                        resource: Some(String::from("/login")),
+                        form: Some(HashMap::from([(String::from("user"), String::from("ann"))])),
+                        ..Detail::new()
+                    }]),
+                    ..Detail::new()
+                },
+            ],
+            "should group by host, without duplicates, cookies or excluded hosts"
+        );
+        assert!(warnings.is_empty());
+
+        let (got, _) = har::import(
+            input,
+            &Import {
+                hosts: vec![],
+                methods: vec![String::from("post")],
+                urls: vec![String::from("*/users*")],
+                strip_auth: true,
+                ..import
+            },
+        )?;
+
+        assert_eq!(got.len(), 1);
+        assert_eq!(
+            got[0].requests.as_ref().map(|requests| requests.len()),
+            Some(1),
+            "should filter by method and URL"
+        );
+
+        Ok(())
+    }
+}
@@ -2,9 +2,16 @@
 mod test {
 
     use actix_web::{web, App, HttpResponse, HttpServer};
-    use corkscrew::executor;
+    use corkscrew::{executor, Policy, Retry};
     use reqwest::StatusCode;
-    use std::{collections::HashMap, error::Error, net::TcpListener};
+    use std::{
+        collections::HashMap,
+        error::Error,
+        net::TcpListener,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static FLAKY_CALLS: AtomicUsize = AtomicUsize::new(0);
 
     #[tokio::test]
     async fn test_executor() -> Result<(), Box<dyn Error>> {
@@ -36,28 +43,127 @@ mod test {
 
         start_server(7878).await;
 
-        let responses = executor::exec(requests).await?;
+        let responses = executor::exec(requests, &HashMap::new(), 0).await?;
 
-        let get_response = responses
+        let get = responses
             .get("test_http_get")
-            .expect("response should be there");
+            .expect("execution should be there");
+        let get_response = get.response.as_ref().expect("response should be there");
 
-        assert_eq!(StatusCode::OK, get_response.status(), "status should be ok");
+        assert_eq!(StatusCode::OK, get_response.status, "status should be ok");
+        assert_eq!(1, get.attempts, "should take a single attempt");
+        assert_eq!(
+            "http_get_ok",
+            get_response.text(),
+            "body should be received"
+        );
+        assert!(
+            get.timings.ttfb <= get.timings.total,
+            "time to first byte should be within total"
+        );
+        assert_eq!(
+            get.timings.total,
+            get.timings.ttfb + get.timings.download,
+            "total should be time to first byte plus download"
+        );
 
         let post_response = responses
             .get("test_http_post")
+            .and_then(|execution| execution.response.as_ref())
+            .expect("response should be there");
+
+        assert_eq!(StatusCode::OK, post_response.status, "status should be ok");
+
+        let unknown = responses
+            .get("test_http_unknown")
+            .expect("execution should be there for unknown request");
+
+        assert!(
+            unknown.response.is_none(),
+            "should not have a response for unknown request"
+        );
+        assert!(
+            unknown
+                .error
+                .as_ref()
+                .expect("should have error for unknown request")
+                .contains("localhost:2323"),
+            "error should describe the failed request"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_retries() -> Result<(), Box<dyn Error>> {
+        let mut requests = HashMap::<String, reqwest::Request>::new();
+
+        requests.insert(
+            String::from("test_http_flaky"),
+            reqwest::Client::new()
+                .get("http://localhost:7879/test_http_flaky_endpoint")
+                .build()
+                .expect("should build request"),
+        );
+
+        requests.insert(
+            String::from("test_http_always_unavailable"),
+            reqwest::Client::new()
+                .get("http://localhost:7879/test_http_unavailable_endpoint")
+                .build()
+                .expect("should build request"),
+        );
+
+        let retry = Retry {
+            attempts: Some(4),
+            delay: Some(1),
+            ..Retry::new()
+        };
+
+        let policy = Policy {
+            retry: Some(retry),
+            rate_limits: vec![],
+            save: None,
+        };
+
+        let policies = HashMap::from([
+            (String::from("test_http_flaky"), policy.clone()),
+            (String::from("test_http_always_unavailable"), policy),
+        ]);
+
+        start_server(7879).await;
+
+        let responses = executor::exec(requests, &policies, 2).await?;
+
+        let flaky = responses
+            .get("test_http_flaky")
             .expect("response should be there");
 
         assert_eq!(
             StatusCode::OK,
-            post_response.status(),
-            "status should be ok"
+            flaky
+                .response
+                .as_ref()
+                .expect("should have response")
+                .status,
+            "should eventually succeed"
         );
+        assert_eq!(3, flaky.attempts, "should succeed on the third attempt");
 
-        assert!(
-            !responses.contains_key("test_http_unknown"),
-            "responses should not contain key for unknown request"
+        let unavailable = responses
+            .get("test_http_always_unavailable")
+            .expect("response should be there");
+
+        assert_eq!(
+            StatusCode::SERVICE_UNAVAILABLE,
+            unavailable
+                .response
+                .as_ref()
+                .expect("should have response")
+                .status,
+            "should return the last response"
         );
+        assert_eq!(4, unavailable.attempts, "should use all attempts");
 
         Ok(())
     }
@@ -67,11 +173,17 @@ mod test {
             TcpListener::bind(format!("localhost:{}", port)).expect("unable to bind to port");
 
         let server = HttpServer::new(move || {
-            App::new().service(
-                web::resource("/test_http_methods_endpoint")
-                    .route(web::get().to(test_http_get_handler))
-                    .route(web::post().to(test_http_post_handler)),
-            )
+            App::new()
+                .service(
+                    web::resource("/test_http_methods_endpoint")
+                        .route(web::get().to(test_http_get_handler))
+                        .route(web::post().to(test_http_post_handler)),
+                )
+                .service(web::resource("/test_http_flaky_endpoint").to(test_http_flaky_handler))
+                .service(
+                    web::resource("/test_http_unavailable_endpoint")
+                        .to(test_http_unavailable_handler),
+                )
         })
         .listen(listener)
         .expect("must be able to start server")
@@ -87,4 +199,18 @@ mod test {
     async fn test_http_post_handler() -> HttpResponse {
         HttpResponse::Ok().body("http_post_ok")
     }
+
+    async fn test_http_flaky_handler() -> HttpResponse {
+        match FLAKY_CALLS.fetch_add(1, Ordering::SeqCst) {
+            0 => HttpResponse::ServiceUnavailable().finish(),
+            1 => HttpResponse::TooManyRequests()
+                .insert_header(("Retry-After", "0"))
+                .finish(),
+            _ => HttpResponse::Ok().body("http_flaky_ok"),
+        }
+    }
+
+    async fn test_http_unavailable_handler() -> HttpResponse {
+        HttpResponse::ServiceUnavailable().finish()
+    }
 }
@@ -0,0 +1,232 @@
+#[cfg(test)]
+mod test {
+    use corkscrew::{import, parser, Import, ImportFormat};
+    use std::{env, error::Error, fs};
+
+    #[test]
+    fn test_import_into_group() -> Result<(), Box<dyn Error>> {
+        let dir = env::temp_dir().join("corkscrew_test_import_into_group");
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir)?;
+
+        let input = dir.join("commands.sh");
+        let into = dir.join("requests.yml");
+
+        fs::write(
+            &input,
+            "curl http://localhost:8080/users -H 'x-test: 1'\ncurl https://example.com/users",
+        )?;
+
+        fs::write(
+            &into,
+            r#"
+- name: api
+  host: localhost
+  port: 8080
+  requests:
+    - name: get_users
+      resource: /users
+"#,
+        )?;
+
+        let imported = import::go(Import {
+            format: ImportFormat::Curl,
+            path: Some(input),
+            into: Some(into.clone()),
+            group: Some(String::from("api")),
+            hosts: vec![],
+            methods: vec![],
+            urls: vec![],
+            strip_cookies: false,
+            strip_auth: false,
+        })?;
+
+        assert_eq!(imported.count, 2);
+        assert!(imported.yaml.is_none(), "should not print merged requests");
+
+        let details = parser::parse(&fs::read_to_string(&into)?, vec![]);
+
+        let names: Vec<&str> = details.iter().filter_map(|d| d.name.as_deref()).collect();
+
+        assert_eq!(
+            names,
+            vec!["get_users", "get_users_2", "get_users_3"],
+            "should nest under group with unique names"
+        );
+
+        assert_eq!(details[1].host.as_deref(), Some("localhost"));
+        assert_eq!(details[1].port, Some(8080));
+
+        assert_eq!(details[2].host.as_deref(), Some("example.com"));
+        assert_eq!(details[2].scheme.as_deref(), Some("https"));
+        assert_eq!(
+            details[2].port,
+            Some(443),
+            "should not inherit the port of the group"
+        );
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_into_group_with_settings() -> Result<(), Box<dyn Error>> {
+        let dir = env::temp_dir().join("corkscrew_test_import_into_group_with_settings");
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir)?;
+
+        let input = dir.join("commands.sh");
+        let into = dir.join("requests.yml");
+
+        fs::write(&input, "curl https://example.com/users")?;
+
+        let source = r#"
+- name: api
+  method: post
+  timeout: 30
+  headers:
+    x-test: "1"
+  body:
+    a: 1
+  requests:
+    - name: add_user
+      resource: /users
+"#;
+
+        fs::write(&into, source)?;
+
+        let import = || Import {
+            format: ImportFormat::Curl,
+            path: Some(input.clone()),
+            into: Some(into.clone()),
+            group: Some(String::from("api")),
+            hosts: vec![],
+            methods: vec![],
+            urls: vec![],
+            strip_cookies: false,
+            strip_auth: false,
+        };
+
+        import::go(import())?;
+
+        let details = parser::parse(&fs::read_to_string(&into)?, vec![]);
+        let imported = &details[1];
+
+        assert_eq!(imported.method.as_deref(), Some("get"));
+        assert_eq!(imported.timeout, Some(10));
+        assert_eq!(
+            (&imported.body, &imported.headers),
+            (&None, &None),
+            "should not inherit settings of the group it doesn't have"
+        );
+
+        let source = "- name: api\n  auth:\n    bearer:\n      token: abc\n  requests: []\n";
+
+        fs::write(&into, source)?;
+
+        assert!(
+            import::go(import()).is_err(),
+            "should not add requests which would inherit auth"
+        );
+        assert_eq!(fs::read_to_string(&into)?, source);
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_into_keeps_comments() -> Result<(), Box<dyn Error>> {
+        let dir = env::temp_dir().join("corkscrew_test_import_into_keeps_comments");
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir)?;
+
+        let input = dir.join("commands.sh");
+        let into = dir.join("requests.yml");
+
+        fs::write(&input, "curl http://localhost:8080/posts")?;
+
+        let source = r#"# Requests for the API
+- name: api # grouped by host
+  host: &host localhost
+  port: 8080
+  requests:
+    # Users
+    - name: get_users
+      resource: /users
+
+# Not part of the API
+- name: other
+  host: *host
+  resource: /other
+"#;
+
+        fs::write(&into, source)?;
+
+        let import = |group: &str| Import {
+            format: ImportFormat::Curl,
+            path: Some(input.clone()),
+            into: Some(into.clone()),
+            group: Some(String::from(group)),
+            hosts: vec![],
+            methods: vec![],
+            urls: vec![],
+            strip_cookies: false,
+            strip_auth: false,
+        };
+
+        import::go(import("api"))?;
+        import::go(import("new"))?;
+
+        let want = r#"# Requests for the API
+- name: api # grouped by host
+  host: &host localhost
+  port: 8080
+  requests:
+    # Users
+    - name: get_users
+      resource: /users
+    - name: get_posts
+      resource: /posts
+
+# Not part of the API
+- name: other
+  host: *host
+  resource: /other
+- name: new
+  requests:
+  - name: get_posts_2
+    host: localhost
+    port: 8080
+    resource: /posts
+"#;
+
+        assert_eq!(
+            fs::read_to_string(&into)?,
+            want,
+            "should add requests, keeping comments, anchors and formatting"
+        );
+
+        let source = "- { name: api, host: localhost, requests: [] } # inline\n";
+
+        fs::write(&into, source)?;
+
+        assert!(
+            import::go(import("api")).is_err(),
+            "should not add requests which can't be added as text"
+        );
+        assert_eq!(
+            fs::read_to_string(&into)?,
+            source,
+            "should leave the file as is"
+        );
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod test {
+    use corkscrew::{Rate, RateLimiter};
+    use std::{
+        error::Error,
+        time::{Duration, Instant},
+    };
+
+    #[tokio::test]
+    async fn test_spaces_requests_to_same_host() -> Result<(), Box<dyn Error>> {
+        let limiter = RateLimiter::new();
+        let rate: Rate = "20/s".parse()?;
+
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire("localhost", &rate).await;
+        }
+
+        assert!(
+            start.elapsed() >= Duration::from_millis(100),
+            "third request should wait two intervals"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_limits_hosts_independently() -> Result<(), Box<dyn Error>> {
+        let limiter = RateLimiter::new();
+        let rate: Rate = "1/m".parse()?;
+
+        let start = Instant::now();
+
+        limiter.acquire("one.localhost", &rate).await;
+        limiter.acquire("two.localhost", &rate).await;
+
+        assert!(
+            start.elapsed() < Duration::from_secs(1),
+            "first request to each host should not wait"
+        );
+
+        Ok(())
+    }
+}
@@ -4,9 +4,9 @@ mod test {
     use std::{collections::HashMap, error::Error, path::PathBuf};
 
     use corkscrew::{
-        parser::parse,
+        parser::{deserialize, parse, parse_selected, suggest},
         settings::Settings,
-        types::{AuthType, Detail},
+        types::{AuthType, Detail, OutputFormat, Retry, Selection},
     };
 
     #[should_panic]
@@ -53,6 +53,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec![],
         };
@@ -69,6 +83,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec![],
         };
@@ -90,6 +118,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec![],
         };
@@ -112,6 +154,15 @@ mod test {
             params: None,
             resource: Some(String::from("/api")),
             requests: None,
+            inherit: None,
+            unset: None,
+            tags: None,
+            variables: None,
+            save_headers: None,
+            save_to: None,
+            output: None,
+            rate_limit: None,
+            retry: None,
         }];
 
         assert_eq!(got, want, "should contruct requests config");
@@ -140,6 +191,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec![],
         };
@@ -163,6 +228,15 @@ mod test {
                 params: None,
                 resource: Some(String::from("/api/0")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
             },
             Detail {
                 name: Some(String::from("test_nested_required_fields_1")),
@@ -180,6 +254,15 @@ mod test {
                 params: None,
                 resource: Some(String::from("/api/1")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
             },
             Detail {
                 name: Some(String::from("test_nested_required_fields_2")),
@@ -197,6 +280,15 @@ mod test {
                 params: None,
                 resource: Some(String::from("/api/2")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
             },
             Detail {
                 name: Some(String::from("test_nested_required_fields_3")),
@@ -214,6 +306,15 @@ mod test {
                 params: None,
                 resource: Some(String::from("/api/3")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
             },
         ];
 
@@ -245,6 +346,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec![],
         };
@@ -268,6 +383,15 @@ mod test {
                 params: None,
                 resource: Some(String::from("/api/1")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
             },
             Detail {
                 name: Some(String::from("test_nested_required_fields_2")),
@@ -285,6 +409,15 @@ mod test {
                 params: None,
                 resource: Some(String::from("/api/2")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
             },
             Detail {
                 name: Some(String::from("test_nested_required_fields_3")),
@@ -302,6 +435,15 @@ mod test {
                 params: None,
                 resource: Some(String::from("/api/3")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
             },
         ];
 
@@ -325,6 +467,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec![],
         };
@@ -350,6 +506,15 @@ mod test {
             params: None,
             resource: Some(String::from("/api")),
             requests: None,
+            inherit: None,
+            unset: None,
+            tags: None,
+            variables: None,
+            save_headers: None,
+            save_to: None,
+            output: None,
+            rate_limit: None,
+            retry: None,
         }];
 
         assert_eq!(
@@ -372,6 +537,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec![],
         };
@@ -396,6 +575,15 @@ mod test {
             params: None,
             resource: Some(String::from("/api")),
             requests: None,
+            inherit: None,
+            unset: None,
+            tags: None,
+            variables: None,
+            save_headers: None,
+            save_to: None,
+            output: None,
+            rate_limit: None,
+            retry: None,
         }];
 
         assert_eq!(
@@ -445,6 +633,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec![],
         };
@@ -462,6 +664,15 @@ mod test {
                 timeout: Some(15),
                 scheme: Some(String::from("https")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
                 auth: None,
                 headers: Some(HashMap::from([
                     (
@@ -505,6 +716,15 @@ mod test {
                 timeout: Some(15),
                 scheme: Some(String::from("https")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
                 auth: None,
                 content: None,
                 form: None,
@@ -566,6 +786,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec!["test_patch_2".to_string()],
         };
@@ -582,6 +816,15 @@ mod test {
             timeout: Some(15),
             scheme: Some(String::from("https")),
             requests: None,
+            inherit: None,
+            unset: None,
+            tags: None,
+            variables: None,
+            save_headers: None,
+            save_to: None,
+            output: None,
+            rate_limit: None,
+            retry: None,
             auth: None,
             content: None,
             form: None,
@@ -639,6 +882,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec!["test_post_1".to_string(), "test_get_3".to_string()],
         };
@@ -656,6 +913,15 @@ mod test {
                 timeout: Some(15),
                 scheme: Some(String::from("https")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
                 auth: None,
                 headers: Some(HashMap::from([
                     (
@@ -698,6 +964,15 @@ mod test {
                 timeout: Some(15),
                 scheme: Some(String::from("https")),
                 requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: None,
                 auth: None,
                 content: None,
                 form: None,
@@ -727,6 +1002,20 @@ mod test {
 
         let settings = Settings {
             parallel: 0,                    // <- not used by parser
+            tags: vec![],                   // <- not used by parser
+            exclude_tags: vec![],           // <- not used by parser
+            allow_duplicates: false,        // <- not used by parser
+            har: None,                      // <- not used by parser
+            save_headers: false,            // <- not used by parser
+            output_dir: None,               // <- not used by parser
+            extract: None,                  // <- not used by parser
+            show_secrets: false,            // <- not used by parser
+            verbose: 0,                     // <- not used by parser
+            reports: vec![],                // <- not used by parser
+            output: OutputFormat::Table,    // <- not used by parser
+            no_color: false,                // <- not used by parser
+            command: None,                  // <- not used by parser
+            rate: None,                     // <- not used by parser
             config_path: PathBuf::from(""), // <- not used by parser
             request_names: vec![String::from("test_form_details")],
         };
@@ -747,6 +1036,15 @@ mod test {
             timeout: None,
             scheme: None,
             requests: None,
+            inherit: None,
+            unset: None,
+            tags: None,
+            variables: None,
+            save_headers: None,
+            save_to: None,
+            output: None,
+            rate_limit: None,
+            retry: None,
             auth: None,
             headers: None,
             params: None,
@@ -759,4 +1057,692 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_retry_inheritance() -> Result<(), Box<dyn Error>> {
+        let source = "
+        - name: test_retry_root
+          host: localhost
+          retry:
+            attempts: 5
+            delay: 200
+            statuses: [502, 503]
+          requests:
+            - name: test_retry_inherited
+              resource: /api/inherited
+            - name: test_retry_overridden
+              resource: /api/overridden
+              retry:
+                attempts: 2
+        ";
+
+        let got = parse(source, vec![]);
+
+        let want = vec![
+            Detail {
+                name: Some(String::from("test_retry_inherited")),
+                host: Some(String::from("localhost")),
+                resource: Some(String::from("/api/inherited")),
+                requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: Some(Retry {
+                    attempts: Some(5),
+                    delay: Some(200),
+                    statuses: Some(vec![502, 503]),
+                    ..Retry::new()
+                }),
+                method: None,
+                hash: None,
+                port: None,
+                timeout: None,
+                scheme: None,
+                auth: None,
+                headers: None,
+                params: None,
+                content: None,
+                form: None,
+                body: None,
+            },
+            Detail {
+                name: Some(String::from("test_retry_overridden")),
+                host: Some(String::from("localhost")),
+                resource: Some(String::from("/api/overridden")),
+                requests: None,
+                inherit: None,
+                unset: None,
+                tags: None,
+                variables: None,
+                save_headers: None,
+                save_to: None,
+                output: None,
+                rate_limit: None,
+                retry: Some(Retry {
+                    attempts: Some(2),
+                    ..Retry::new()
+                }),
+                method: None,
+                hash: None,
+                port: None,
+                timeout: None,
+                scheme: None,
+                auth: None,
+                headers: None,
+                params: None,
+                content: None,
+                form: None,
+                body: None,
+            },
+        ];
+
+        assert_eq!(got, want, "should inherit or override retry config");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_variables() -> Result<(), Box<dyn Error>> {
+        let source = "
+        - name: test_variables_root
+          host: '{{env}}.example.com'
+          variables:
+            env: api
+            token: abc
+          auth: !bearer
+            token: '{{token}}'
+          requests:
+            - name: test_variables_user
+              resource: /users/{{ id }}
+              variables:
+                env: staging
+                id: '42'
+              headers:
+                X-Request: '{{id}}'
+              body:
+                ids: ['{{id}}', '{{ not a variable }}']
+        ";
+
+        let got = parse(source, vec![]);
+
+        let want = vec![Detail {
+            name: Some(String::from("test_variables_user")),
+            host: Some(String::from("staging.example.com")),
+            resource: Some(String::from("/users/42")),
+            variables: Some(HashMap::from([
+                (String::from("env"), String::from("staging")),
+                (String::from("id"), String::from("42")),
+                (String::from("token"), String::from("abc")),
+            ])),
+            auth: Some(AuthType::Bearer {
+                token: String::from("abc"),
+            }),
+            headers: Some(HashMap::from([(
+                String::from("X-Request"),
+                String::from("42"),
+            )])),
+            body: Some(serde_json::json!({ "ids": ["42", "{{ not a variable }}"] })),
+            ..Detail::new()
+        }];
+
+        assert_eq!(
+            got, want,
+            "should merge inherited variables and substitute them"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Variable 'id' is not defined for 'test_undefined'.")]
+    fn test_parse_undefined_variable() {
+        let source = "
+        - name: test_undefined
+          host: localhost
+          resource: /users/{{id}}
+          variables:
+            env: test
+        ";
+
+        parse(source, vec![]);
+    }
+
+    #[test]
+    fn test_parse_escaped_variables() -> Result<(), Box<dyn Error>> {
+        let source = r#"
+        - name: test_escaped
+          host: localhost
+          resource: /users
+          variables:
+            id: '42'
+            template: '\{{greeting}}, {{id}}'
+          body:
+            template: '\{{name}} is {{ id }}'
+            message: '{{template}}'
+        - name: test_no_variables
+          host: localhost
+          resource: /users/{{id}}
+          body:
+            template: 'Hello, {{name}}'
+        "#;
+
+        let got: Vec<Option<serde_json::Value>> = parse(source, vec![])
+            .into_iter()
+            .map(|detail| detail.body)
+            .collect();
+
+        assert_eq!(
+            got,
+            vec![
+                Some(serde_json::json!({
+                    "template": "{{name}} is 42",
+                    "message": "{{greeting}}, 42",
+                })),
+                Some(serde_json::json!({ "template": "Hello, {{name}}" })),
+            ],
+            "should keep escaped variables and leave requests without variables as they are"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_top_level_requests_are_independent() -> Result<(), Box<dyn Error>> {
+        let source = "
+        - name: test_first
+          host: localhost
+          resource: /first
+          headers:
+            X-First: '1'
+        - name: test_second
+          host: example.com
+          resource: /second
+        ";
+
+        let got = parse(source, vec![String::from("test_second")]);
+
+        let want = vec![Detail {
+            name: Some(String::from("test_second")),
+            host: Some(String::from("example.com")),
+            resource: Some(String::from("/second")),
+            ..Detail::new()
+        }];
+
+        assert_eq!(got, want, "should not inherit from a sibling");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_unknown_fields() -> Result<(), Box<dyn Error>> {
+        let source = "
+- name: test_unknown
+  host: localhost
+  resource: /api
+  parms:
+    page: '1'
+";
+
+        assert_eq!(
+            deserialize(source),
+            Err(String::from(
+                "5:3: unknown field 'parms' (did you mean 'params'?)"
+            ))
+        );
+
+        let source = "
+- name: test_unknown
+  host: localhost
+  retry:
+    atempts: 2
+";
+
+        assert_eq!(
+            deserialize(source),
+            Err(String::from(
+                "5:5: unknown field 'atempts' (did you mean 'attempts'?)"
+            )),
+            "should reject unknown fields of nested settings"
+        );
+
+        let source = "
+- name: test_unknown
+  xyzzy: true
+";
+
+        assert!(
+            deserialize(source).is_err_and(|e| e.starts_with("3:3: unknown field 'xyzzy', expected one of 'name'")),
+            "should list expected fields without a close match"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown field 'header' (did you mean 'headers'?)")]
+    fn test_parse_unknown_field() {
+        let source = "
+        - name: test_unknown
+          host: localhost
+          resource: /api
+          header:
+            Accept: application/json
+        ";
+
+        parse(source, vec![]);
+    }
+
+    #[test]
+    fn test_suggest() -> Result<(), Box<dyn Error>> {
+        let fields = ["host", "headers", "params", "resource"];
+
+        assert_eq!(suggest("hots", fields), Some("host"), "should allow swaps");
+        assert_eq!(suggest("resouce", fields), Some("resource"));
+        assert_eq!(suggest("param", fields), Some("params"));
+        assert_eq!(
+            suggest("body", fields),
+            None,
+            "should not suggest unrelated names"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_qualifies_shared_names() -> Result<(), Box<dyn Error>> {
+        let source = "
+        - name: test_api
+          host: localhost
+          requests:
+            - name: test_users
+              requests:
+                - name: test_get
+                  resource: /users
+            - name: test_posts
+              requests:
+                - name: test_get
+                  resource: /posts
+                - name: test_create
+                  resource: /posts
+                  method: post
+        ";
+
+        let names = |request_names: Vec<&str>| -> Vec<String> {
+            let request_names = request_names.into_iter().map(String::from).collect();
+
+            parse(source, request_names)
+                .into_iter()
+                .filter_map(|detail| detail.name)
+                .collect()
+        };
+
+        assert_eq!(
+            names(vec![]),
+            vec![
+                "test_api.test_users.test_get",
+                "test_api.test_posts.test_get",
+                "test_create"
+            ],
+            "should name requests by their path only where names are shared"
+        );
+
+        assert_eq!(
+            names(vec!["test_api.test_posts.test_get"]),
+            vec!["test_api.test_posts.test_get"],
+            "should select by qualified name"
+        );
+
+        assert_eq!(
+            names(vec!["test_get", "test_create"]),
+            vec![
+                "test_api.test_users.test_get",
+                "test_api.test_posts.test_get",
+                "test_create"
+            ],
+            "should select every request with a shared name"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "More than one request is named 'test_get'")]
+    fn test_parse_duplicate_names() {
+        let source = "
+        - name: test_get
+          host: localhost
+          resource: /first
+        - name: test_get
+          host: localhost
+          resource: /second
+        ";
+
+        parse(source, vec![]);
+    }
+
+    #[test]
+    fn test_parse_allow_duplicate_names() -> Result<(), Box<dyn Error>> {
+        let source = "
+        - name: test_api
+          host: localhost
+          resource: /api
+          requests:
+            - resource: /api/unnamed
+              requests: []
+        - name: test_api
+          host: localhost
+          resource: /other
+        ";
+
+        let got = parse_selected(
+            source,
+            &Selection {
+                allow_duplicates: true,
+                ..Selection::default()
+            },
+        );
+
+        let names: Vec<&str> = got
+            .iter()
+            .filter_map(|(_, detail)| detail.name.as_deref())
+            .collect();
+
+        assert_eq!(
+            names,
+            vec!["test_api", "test_api.test_api", "test_api"],
+            "should keep duplicates, naming unnamed requests by their path"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_select_by_path() -> Result<(), Box<dyn Error>> {
+        let source = "
+        - name: test_users
+          host: localhost
+          requests:
+            - name: test_get_user
+              resource: /users/1
+            - name: test_delete_user
+              resource: /users/1
+              method: delete
+            - name: test_admin
+              requests:
+                - name: test_delete_all
+                  resource: /users
+                  method: delete
+        - name: test_delete_cache
+          host: localhost
+          resource: /cache
+          method: delete
+        ";
+
+        let names = |selector: &str| -> Vec<String> {
+            parse(source, vec![String::from(selector)])
+                .into_iter()
+                .filter_map(|detail| detail.name)
+                .collect()
+        };
+
+        assert_eq!(
+            names("test_users:test_get_user"),
+            vec!["test_get_user"],
+            "should select by path"
+        );
+        assert_eq!(
+            names("test_users"),
+            vec!["test_get_user", "test_delete_user", "test_delete_all"],
+            "should select everything nested in a group"
+        );
+        assert_eq!(
+            names("test_admin"),
+            vec!["test_delete_all"],
+            "should select nested groups by name"
+        );
+        assert_eq!(
+            names("test_users:*"),
+            vec!["test_get_user", "test_delete_user", "test_delete_all"]
+        );
+        assert_eq!(
+            names("**:test_delete_*"),
+            vec!["test_delete_user", "test_delete_all", "test_delete_cache"],
+            "should match any number of groups"
+        );
+        assert_eq!(
+            names("test_admin:*"),
+            Vec::<String>::new(),
+            "should match from the top"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_tags() -> Result<(), Box<dyn Error>> {
+        let source = "
+        - name: test_api
+          host: localhost
+          tags: [api]
+          requests:
+            - name: test_get
+              resource: /users
+              tags: [smoke]
+            - name: test_delete
+              resource: /users
+              method: delete
+              tags: [destructive, api]
+            - name: test_list
+              resource: /users
+        ";
+
+        let got = parse(source, vec![]);
+
+        assert_eq!(
+            got[1].tags,
+            Some(vec![String::from("api"), String::from("destructive")]),
+            "should merge inherited tags, without repeating any"
+        );
+
+        let names = |tags: Vec<&str>, exclude_tags: Vec<&str>| -> Vec<String> {
+            let selection = Selection {
+                tags: tags.into_iter().map(String::from).collect(),
+                exclude_tags: exclude_tags.into_iter().map(String::from).collect(),
+                ..Selection::default()
+            };
+
+            parse_selected(source, &selection)
+                .into_iter()
+                .filter_map(|(_, detail)| detail.name)
+                .collect()
+        };
+
+        assert_eq!(names(vec!["smoke"], vec![]), vec!["test_get"]);
+        assert_eq!(
+            names(vec!["smoke", "destructive"], vec![]),
+            vec!["test_get", "test_delete"],
+            "should select requests with any of the tags"
+        );
+        assert_eq!(
+            names(vec!["api"], vec!["destructive"]),
+            vec!["test_get", "test_list"],
+            "should leave out excluded tags"
+        );
+
+        let got: Vec<String> = parse_selected(
+            source,
+            &Selection {
+                names: vec![String::from("test_get"), String::from("test_delete")],
+                exclude_tags: vec![String::from("destructive")],
+                ..Selection::default()
+            },
+        )
+        .into_iter()
+        .filter_map(|(_, detail)| detail.name)
+        .collect();
+
+        assert_eq!(got, vec!["test_get"], "should combine with names");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_merged_inheritance() -> Result<(), Box<dyn Error>> {
+        let source = "
+        - name: test_api
+          host: localhost
+          headers:
+            Accept: application/json
+            Authorization: Bearer abc
+            X-Trace: '1'
+          params:
+            page: '1'
+            limit: '10'
+          body:
+            user:
+              name: test
+              role: admin
+            active: true
+          requests:
+            - name: test_merged
+              resource: /merged
+              headers:
+                accept: text/plain
+                X-Client: test
+              params:
+                limit: '20'
+              unset:
+                headers: [x-trace]
+                params: [page]
+            - name: test_not_inherited
+              resource: /not_inherited
+              inherit:
+                headers: false
+                params: replace
+                body: merge
+              headers:
+                X-Client: test
+              body:
+                user:
+                  role: null
+                  email: test@example.com
+        ";
+
+        let got = parse(source, vec![]);
+
+        assert_eq!(
+            got[0].headers,
+            Some(HashMap::from([
+                (String::from("accept"), String::from("text/plain")),
+                (String::from("Authorization"), String::from("Bearer abc")),
+                (String::from("X-Client"), String::from("test")),
+            ])),
+            "should merge headers, ignoring case, less those unset"
+        );
+        assert_eq!(
+            got[0].params,
+            Some(HashMap::from([(String::from("limit"), String::from("20"))])),
+            "should merge params, less those unset"
+        );
+        assert_eq!(got[0].inherit, None, "should not keep how it inherits");
+
+        assert_eq!(
+            got[1].headers,
+            Some(HashMap::from([(
+                String::from("X-Client"),
+                String::from("test")
+            )])),
+            "should not inherit headers"
+        );
+        assert_eq!(
+            got[1].params,
+            Some(HashMap::from([
+                (String::from("page"), String::from("1")),
+                (String::from("limit"), String::from("10")),
+            ])),
+            "should inherit params it doesn't replace"
+        );
+        assert_eq!(
+            got[1].body,
+            Some(serde_json::json!({
+                "user": { "name": "test", "email": "test@example.com" },
+                "active": true,
+            })),
+            "should merge the body as a merge patch"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_replaced_body() -> Result<(), Box<dyn Error>> {
+        let source = "
+        - name: test_api
+          host: localhost
+          body:
+            user: test
+          requests:
+            - name: test_replaced
+              resource: /replaced
+              body:
+                id: 1
+            - name: test_inherited
+              resource: /inherited
+            - name: test_no_body
+              resource: /no_body
+              inherit:
+                body: false
+        ";
+
+        let got: Vec<Option<serde_json::Value>> = parse(source, vec![])
+            .into_iter()
+            .map(|detail| detail.body)
+            .collect();
+
+        assert_eq!(
+            got,
+            vec![
+                Some(serde_json::json!({ "id": 1 })),
+                Some(serde_json::json!({ "user": "test" })),
+                None,
+            ],
+            "should replace the body by default"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_paths_of_unnamed_groups() -> Result<(), Box<dyn Error>> {
+        let source = "
+        - name: users
+          host: localhost
+          requests:
+            - resource: /users
+              requests:
+                - name: list
+                  resource: /users
+        ";
+
+        let got: Vec<Vec<String>> = parse_selected(source, &Selection::default())
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+
+        assert_eq!(
+            got,
+            vec![
+                vec![String::from("users"), String::from("users")],
+                vec![String::from("users"), String::from("list")],
+            ],
+            "should not repeat the name an unnamed group inherits"
+        );
+
+        Ok(())
+    }
 }
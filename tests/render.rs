@@ -0,0 +1,541 @@
+#[cfg(test)]
+mod test {
+    use corkscrew::{
+        render, Detail, Extract, Output, RequestOutcome, RequestSnapshot, ResponseSnapshot,
+        RunReport, Timings,
+    };
+    use serde_json::json;
+    use std::{
+        error::Error,
+        time::{Duration, SystemTime},
+    };
+
+    fn report() -> RunReport {
+        RunReport {
+            outcomes: vec![
+                RequestOutcome {
+                    name: String::from("test_get"),
+                    path: vec![String::from("test_api"), String::from("test_get")],
+                    detail: Detail::new(),
+                    request: RequestSnapshot {
+                        method: String::from("GET"),
+                        url: String::from("http://localhost/api/get"),
+                        headers: vec![],
+                        body: None,
+                    },
+                    response: Some(ResponseSnapshot {
+                        status: 200,
+                        version: String::from("HTTP/1.1"),
+                        url: String::from("http://localhost/api/get"),
+                        headers: vec![(
+                            String::from("content-type"),
+                            String::from("application/json"),
+                        )],
+                        body: vec![b'x'; 2048],
+                        saved: None,
+                    }),
+                    error: None,
+                    attempts: 1,
+                    timings: Timings::default(),
+                    started: SystemTime::UNIX_EPOCH,
+                    duration: Duration::from_millis(42),
+                },
+                RequestOutcome {
+                    name: String::from("test_unreachable"),
+                    path: vec![String::from("test_unreachable")],
+                    detail: Detail::new(),
+                    request: RequestSnapshot {
+                        method: String::from("POST"),
+                        url: String::from("http://localhost:2323/"),
+                        headers: vec![],
+                        body: None,
+                    },
+                    response: None,
+                    error: Some(String::from("connection refused")),
+                    attempts: 1,
+                    timings: Timings::default(),
+                    started: SystemTime::UNIX_EPOCH,
+                    duration: Duration::from_millis(3),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_output_rows() -> Result<(), Box<dyn Error>> {
+        let report = report();
+
+        let got: Vec<Output> = report.outcomes.iter().map(Output::from).collect();
+
+        let want = vec![
+            Output {
+                name: String::from("test_get"),
+                method: String::from("GET"),
+                url: String::from("http://localhost/api/get"),
+                status: String::from("200"),
+                duration: String::from("42ms"),
+                size: String::from("2.0 KiB"),
+            },
+            Output {
+                name: String::from("test_unreachable"),
+                method: String::from("POST"),
+                url: String::from("http://localhost:2323/"),
+                status: String::from("error"),
+                duration: String::from("3ms"),
+                size: String::from(""),
+            },
+        ];
+
+        assert_eq!(got, want, "should have a row per outcome in order");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_display() -> Result<(), Box<dyn Error>> {
+        let got = render::table::table(&report(), false)
+            .display()?
+            .to_string();
+
+        let lines: Vec<&str> = got.lines().collect();
+
+        assert!(lines[1].contains("Name"), "should have title row");
+        assert!(lines[1].contains("Duration"), "should have title row");
+        assert!(lines[3].contains("test_get"), "should list first request");
+        assert!(
+            lines[5].contains("test_unreachable"),
+            "should list second request"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_records() -> Result<(), Box<dyn Error>> {
+        let mut report = report();
+
+        if let Some(response) = report.outcomes[0].response.as_mut() {
+            response.body = br#"{"id": 1}"#.to_vec();
+        }
+
+        let got: serde_json::Value = serde_json::from_str(&render::json::json(&report)?)?;
+
+        assert_eq!(got[0]["name"], json!("test_get"));
+        assert_eq!(got[0]["status"], json!(200));
+        assert_eq!(got[0]["headers"]["content-type"], json!("application/json"));
+        assert_eq!(
+            got[0]["body"],
+            json!({ "id": 1 }),
+            "should embed JSON bodies"
+        );
+        assert_eq!(got[0]["timings"]["duration"], json!(42.0));
+        assert_eq!(got[1]["status"], json!(null));
+        assert_eq!(got[1]["error"], json!("connection refused"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ndjson_record() -> Result<(), Box<dyn Error>> {
+        let report = report();
+
+        let got = render::json::ndjson(&report.outcomes[0])?;
+
+        assert_eq!(got.lines().count(), 1, "should be a single line");
+
+        let record: serde_json::Value = serde_json::from_str(&got)?;
+
+        assert_eq!(record["url"], json!("http://localhost/api/get"));
+        assert_eq!(
+            record["body"],
+            json!("x".repeat(2048)),
+            "should include non-JSON bodies as text"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_junit_report() -> Result<(), Box<dyn Error>> {
+        let mut report = report();
+
+        report.outcomes.push(RequestOutcome {
+            name: String::from("test_missing"),
+            path: vec![String::from("test_api"), String::from("test_missing")],
+            response: Some(ResponseSnapshot {
+                status: 404,
+                version: String::from("HTTP/1.1"),
+                url: String::from("http://localhost/api/missing?a=1&b=2"),
+                headers: vec![],
+                body: vec![],
+                saved: None,
+            }),
+            request: RequestSnapshot {
+                method: String::from("GET"),
+                url: String::from("http://localhost/api/missing?a=1&b=2"),
+                headers: vec![],
+                body: None,
+            },
+            ..report.outcomes[0].clone()
+        });
+
+        let got = render::junit::junit(&report);
+
+        assert!(got.contains(
+            r#"<testsuites name="corkscrew" tests="3" failures="1" errors="1" time="0.087">"#
+        ));
+        assert!(
+            got.contains(
+                r#"<testsuite name="test_api" tests="2" failures="1" errors="0" time="0.084">"#
+            ),
+            "should group nested requests into a suite"
+        );
+        assert!(got.contains(r#"<testcase name="test_get" classname="test_api" time="0.042"/>"#));
+        assert!(
+            got.contains(r#"<failure type="status" message="GET http://localhost/api/missing?a=1&amp;b=2 returned 404"/>"#),
+            "should fail on error statuses and escape attributes"
+        );
+        assert!(
+            got.contains(
+                r#"<testsuite name="corkscrew" tests="1" failures="0" errors="1" time="0.003">"#
+            ),
+            "should put top level requests in a default suite"
+        );
+        assert!(got.contains(r#"<error type="request" message="connection refused"/>"#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tap_report() -> Result<(), Box<dyn Error>> {
+        let got = render::tap::tap(&report());
+
+        let want = r#"TAP version 13
+1..2
+ok 1 - test_api.test_get
+not ok 2 - test_unreachable
+  ---
+  message: "connection refused"
+  severity: error
+  duration_ms: 3
+  ...
+"#;
+
+        assert_eq!(got, want);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verbose() -> Result<(), Box<dyn Error>> {
+        let mut outcome = report().outcomes[0].clone();
+
+        outcome.request.headers = vec![
+            (String::from("authorization"), String::from("Bearer abc123")),
+            (String::from("cookie"), String::from("session=abc123")),
+            (String::from("accept"), String::from("application/json")),
+        ];
+        outcome.request.body = Some(br#"{"id":1}"#.to_vec());
+
+        if let Some(response) = outcome.response.as_mut() {
+            response.body = br#"{"ok":true}"#.to_vec();
+        }
+
+        let got = render::verbose::verbose(&outcome, 1, false, false);
+
+        let want = r#"* test_get
+> GET http://localhost/api/get
+> authorization: Bearer ********
+> cookie: ********
+> accept: application/json
+>
+< 200 OK
+< content-type: application/json
+<
+"#;
+
+        assert_eq!(got, want, "should mask secrets and leave out bodies");
+
+        let got = render::verbose::verbose(&outcome, 2, true, false);
+
+        assert!(got.contains("> authorization: Bearer abc123\n"));
+        assert!(got.contains(">\n{\"id\":1}\n< 200 OK\n"));
+        assert!(
+            got.ends_with("<\n{\n  \"ok\": true\n}\n"),
+            "should include formatted bodies"
+        );
+
+        Ok(())
+    }
+
+    fn content_type(value: &str) -> Vec<(String, String)> {
+        vec![(String::from("Content-Type"), String::from(value))]
+    }
+
+    #[test]
+    fn test_body_json() -> Result<(), Box<dyn Error>> {
+        let headers = content_type("application/problem+json");
+
+        let got = render::body::body(&headers, br#"{"id":1,"tags":["a"]}"#, false);
+
+        assert_eq!(got, "{\n  \"id\": 1,\n  \"tags\": [\n    \"a\"\n  ]\n}");
+
+        let got = render::body::body(&headers, br#"{"id":1}"#, true);
+
+        assert_eq!(
+            got, "{\n  \x1b[34m\"id\"\x1b[0m: \x1b[33m1\x1b[0m\n}",
+            "should highlight keys and values"
+        );
+
+        let got = render::body::body(&headers, b"{not json", false);
+
+        assert_eq!(got, "{not json", "should show invalid JSON as is");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_body_markup() -> Result<(), Box<dyn Error>> {
+        let got = render::body::body(
+            &content_type("application/xml"),
+            br#"<?xml version="1.0"?><users><user id="1"><name>Ann &amp; Bo</name></user><user id="2"/></users>"#,
+            false,
+        );
+
+        let want = r#"<?xml version="1.0"?>
+<users>
+  <user id="1">
+    <name>
+      Ann &amp; Bo
+    </name>
+  </user>
+  <user id="2"/>
+</users>
+"#;
+
+        assert_eq!(got, want);
+
+        let got = render::body::body(
+            &content_type("text/html; charset=utf-8"),
+            b"<!DOCTYPE html><html><head><meta charset=\"utf-8\"><script>if (a < b) {}</script></head><body><br><p>Hi</p></body></html>",
+            false,
+        );
+
+        let want = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="utf-8">
+    <script>
+if (a < b) {}
+    </script>
+  </head>
+  <body>
+    <br>
+    <p>
+      Hi
+    </p>
+  </body>
+</html>
+"#;
+
+        assert_eq!(got, want, "should handle void and raw HTML elements");
+
+        let got = render::body::body(
+            &content_type("text/html"),
+            "<script>\u{212A} = 'İ'</SCRIPT><p>Hi</p>".as_bytes(),
+            false,
+        );
+
+        let want = "<script>\n\u{212A} = 'İ'\n</SCRIPT>\n<p>\n  Hi\n</p>\n";
+
+        assert_eq!(
+            got, want,
+            "should find the closing tag after text which changes length when lowercased"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_body_charset() -> Result<(), Box<dyn Error>> {
+        let got = render::body::body(
+            &content_type("text/plain; charset=ISO-8859-1"),
+            &[b'c', b'a', b'f', 0xe9],
+            false,
+        );
+
+        assert_eq!(got, "café", "should decode using charset");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_body_binary() -> Result<(), Box<dyn Error>> {
+        let png = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+        let got = render::body::body(&content_type("image/png"), &png, false);
+
+        assert_eq!(
+            got,
+            "[binary, 8 B, image/png]\n00000000  89 50 4e 47 0d 0a 1a 0a                          .PNG....\n"
+        );
+
+        let got = render::body::body(&[], &[0xff; 100], false);
+
+        assert!(
+            got.starts_with("[binary, 100 B, unknown type]\n"),
+            "should treat undecodable bodies as binary"
+        );
+        assert!(got.ends_with("...\n"), "should only dump the first bytes");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_values() -> Result<(), Box<dyn Error>> {
+        let mut report = report();
+
+        assert_eq!(
+            render::extract::values(&report, None),
+            None,
+            "should not extract unless configured"
+        );
+
+        report.outcomes[0].detail.output = Some(Extract {
+            header: Some(String::from("Content-Type")),
+            ..Extract::new()
+        });
+
+        assert_eq!(
+            render::extract::values(&report, None),
+            Some(vec![String::from("application/json")]),
+            "should extract using request output setting"
+        );
+
+        if let Some(response) = report.outcomes[0].response.as_mut() {
+            response.body = br#"{"data": [{"id": 1}, {"id": 2}]}"#.to_vec();
+        }
+
+        let extract = Extract {
+            select: Some("$.data[*].id".parse()?),
+            ..Extract::new()
+        };
+
+        assert_eq!(
+            render::extract::values(&report, Some(&extract)),
+            Some(vec![String::from("1"), String::from("2")]),
+            "should override request output setting"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_har() -> Result<(), Box<dyn Error>> {
+        let mut report = report();
+
+        report.outcomes[0].detail.body = Some(json!({ "id": 1 }));
+        report.outcomes[0].request.body = Some(br#"{"id":1}"#.to_vec());
+        report.outcomes[0].request.url = String::from("http://localhost/api/get?q=a+b");
+        report.outcomes[0].request.headers = vec![
+            (String::from("cookie"), String::from("a=1; b=2")),
+            (
+                String::from("content-type"),
+                String::from("application/json"),
+            ),
+        ];
+        report.outcomes[0].timings = Timings {
+            dns: Some(Duration::from_millis(2)),
+            ttfb: Duration::from_millis(30),
+            download: Duration::from_millis(10),
+            total: Duration::from_millis(40),
+        };
+
+        if let Some(response) = report.outcomes[0].response.as_mut() {
+            response.body = vec![0xff, 0x00, 0x10];
+        }
+
+        let got: serde_json::Value = serde_json::from_str(&render::har::har(&report, false)?)?;
+
+        let entries = &got["log"]["entries"];
+
+        assert_eq!(got["log"]["version"], json!("1.2"));
+        assert_eq!(
+            entries[0]["startedDateTime"],
+            json!("1970-01-01T00:00:00.000Z")
+        );
+        assert_eq!(entries[0]["time"], json!(40.0));
+        assert_eq!(
+            entries[0]["timings"],
+            json!({
+                "blocked": -1, "dns": 2.0, "connect": -1, "ssl": -1,
+                "send": 0, "wait": 28.0, "receive": 10.0,
+            }),
+            "should add up to the time taken"
+        );
+        assert_eq!(
+            entries[0]["request"]["queryString"],
+            json!([{ "name": "q", "value": "a b" }])
+        );
+        assert_eq!(
+            entries[0]["request"]["cookies"],
+            json!([{ "name": "a", "value": "********" }, { "name": "b", "value": "********" }]),
+            "should mask cookies"
+        );
+        assert_eq!(
+            entries[0]["request"]["headers"][0],
+            json!({ "name": "cookie", "value": "********" })
+        );
+        assert_eq!(
+            entries[0]["request"]["postData"],
+            json!({ "mimeType": "application/json", "text": "{\"id\":1}" })
+        );
+        assert_eq!(entries[0]["response"]["statusText"], json!("OK"));
+        assert_eq!(
+            entries[0]["response"]["content"],
+            json!({
+                "size": 3,
+                "mimeType": "application/json",
+                "text": "/wAQ",
+                "encoding": "base64",
+            }),
+            "should encode binary bodies"
+        );
+
+        // Bodies ending in 1, 2 and 3 bytes
+        for (body, text) in [
+            (vec![0xff, 0x00, 0x10, 0xff], "/wAQ/w=="),
+            (vec![0xff, 0x00, 0x10, 0xff, 0xfe], "/wAQ//4="),
+            (vec![0xff, 0x00, 0x10, 0xff, 0xfe, 0xfd], "/wAQ//79"),
+        ] {
+            let mut report = report.clone();
+
+            if let Some(response) = report.outcomes[0].response.as_mut() {
+                response.body = body;
+            }
+
+            let got: serde_json::Value = serde_json::from_str(&render::har::har(&report, false)?)?;
+
+            assert_eq!(
+                got["log"]["entries"][0]["response"]["content"]["text"],
+                json!(text)
+            );
+        }
+        assert_eq!(entries[1]["response"]["status"], json!(0));
+        assert_eq!(
+            entries[1]["response"]["_error"],
+            json!("connection refused")
+        );
+        assert_eq!(entries[1]["timings"]["wait"], json!(3.0));
+
+        let got: serde_json::Value = serde_json::from_str(&render::har::har(&report, true)?)?;
+
+        assert_eq!(
+            got["log"]["entries"][0]["request"]["cookies"],
+            json!([{ "name": "a", "value": "1" }, { "name": "b", "value": "2" }]),
+            "should show secrets if asked"
+        );
+
+        Ok(())
+    }
+}
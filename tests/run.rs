@@ -1,7 +1,7 @@
-use std::{error::Error, net::TcpListener, path::PathBuf};
+use std::{error::Error, net::TcpListener, path::PathBuf, sync::Once, thread};
 
 use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
-use corkscrew::{run, Settings};
+use corkscrew::{run, OutputFormat, Settings};
 
 #[derive(serde::Deserialize)]
 struct TestUrlParams {
@@ -29,23 +29,43 @@ async fn test_run_e2e() -> Result<(), Box<dyn Error>> {
     let settings = Settings {
         config_path: PathBuf::from("tests/e2e-config.yml"),
         parallel: 0,
+        tags: vec![],
+        exclude_tags: vec![],
+        allow_duplicates: false,
+        har: None,
+        save_headers: false,
+        output_dir: None,
+        extract: None,
+        show_secrets: false,
+        verbose: 0,
+        reports: vec![],
+        output: OutputFormat::Table,
+        no_color: false,
+        command: None,
+        rate: None,
         request_names: vec![String::from("request_one"), String::from("request_two")],
     };
 
     start_server(7878).await;
 
-    let mut results = run::go(settings).await?;
+    let report = run::go(settings).await?;
 
-    let r1 = results.remove("request_one").expect("should have response");
-    assert_eq!(200, r1.status(), "should be successful");
+    let r1 = report
+        .get("request_one")
+        .and_then(|outcome| outcome.response.as_ref())
+        .expect("should have response");
+    assert_eq!(200, r1.status, "should be successful");
 
-    let t1 = r1.text().await?;
+    let t1 = r1.text();
     assert_eq!("handler_one_ok", t1);
 
-    let r2 = results.remove("request_two").expect("should have response");
-    assert_eq!(200, r2.status(), "should be successful");
+    let r2 = report
+        .get("request_two")
+        .and_then(|outcome| outcome.response.as_ref())
+        .expect("should have response");
+    assert_eq!(200, r2.status, "should be successful");
 
-    let t2 = r2.text().await?;
+    let t2 = r2.text();
     assert_eq!("handler_two_ok", t2);
 
     Ok(())
@@ -56,6 +76,20 @@ async fn test_http_methods() -> Result<(), Box<dyn Error>> {
     let settings = Settings {
         config_path: PathBuf::from("tests/e2e-config.yml"),
         parallel: 0,
+        tags: vec![],
+        exclude_tags: vec![],
+        allow_duplicates: false,
+        har: None,
+        save_headers: false,
+        output_dir: None,
+        extract: None,
+        show_secrets: false,
+        verbose: 0,
+        reports: vec![],
+        output: OutputFormat::Table,
+        no_color: false,
+        command: None,
+        rate: None,
         request_names: vec![
             String::from("http_put"),
             String::from("http_patch"),
@@ -67,79 +101,266 @@ async fn test_http_methods() -> Result<(), Box<dyn Error>> {
 
     start_server(7878).await;
 
-    let mut results = run::go(settings).await?;
+    let report = run::go(settings).await?;
 
-    let put = results
-        .remove("http_put")
+    let put = report
+        .get("http_put")
+        .and_then(|outcome| outcome.response.as_ref())
         .expect("should have the put response");
 
     println!("PUT: {:#?}", put);
 
-    assert_eq!(
-        put.status(),
-        reqwest::StatusCode::OK,
-        "put should return ok"
-    );
-    assert_eq!(
-        put.text().await.expect("should have response text"),
-        "put_ok"
-    );
+    assert_eq!(put.status, reqwest::StatusCode::OK, "put should return ok");
+    assert_eq!(put.text(), "put_ok");
 
-    let patch = results
-        .remove("http_patch")
+    let patch = report
+        .get("http_patch")
+        .and_then(|outcome| outcome.response.as_ref())
         .expect("should have the patch response");
 
     assert_eq!(
-        patch.status(),
+        patch.status,
         reqwest::StatusCode::OK,
         "patch should return ok"
     );
-    assert_eq!(
-        patch.text().await.expect("should have response text"),
-        "patch_ok"
-    );
+    assert_eq!(patch.text(), "patch_ok");
 
-    let delete = results
-        .remove("http_delete")
+    let delete = report
+        .get("http_delete")
+        .and_then(|outcome| outcome.response.as_ref())
         .expect("should have the delete response");
 
     assert_eq!(
-        delete.status(),
+        delete.status,
         reqwest::StatusCode::OK,
         "delete should return ok"
     );
-    assert_eq!(
-        delete.text().await.expect("should have response text"),
-        "delete_ok"
-    );
+    assert_eq!(delete.text(), "delete_ok");
 
-    let get = results
-        .remove("http_get")
+    let get = report
+        .get("http_get")
+        .and_then(|outcome| outcome.response.as_ref())
         .expect("should have the get response");
 
+    assert_eq!(get.status, reqwest::StatusCode::OK, "get should return ok");
+    assert_eq!(get.text(), "get_ok");
+
+    let default = report
+        .get("http_default")
+        .and_then(|outcome| outcome.response.as_ref())
+        .expect("should have the default (get) response");
+
     assert_eq!(
-        get.status(),
+        default.status,
         reqwest::StatusCode::OK,
-        "get should return ok"
+        "default (get) should return ok",
     );
+    assert_eq!(default.text(), "get_ok");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_report() -> Result<(), Box<dyn Error>> {
+    let settings = Settings {
+        config_path: PathBuf::from("tests/e2e-config.yml"),
+        parallel: 4,
+        tags: vec![],
+        exclude_tags: vec![],
+        allow_duplicates: false,
+        har: None,
+        save_headers: false,
+        output_dir: None,
+        extract: None,
+        show_secrets: false,
+        verbose: 0,
+        reports: vec![],
+        output: OutputFormat::Table,
+        no_color: false,
+        command: None,
+        rate: None,
+        request_names: vec![
+            String::from("http_default"),
+            String::from("request_two"),
+            String::from("http_put"),
+        ],
+    };
+
+    start_server(7878).await;
+
+    let report = run::go(settings).await?;
+
+    let names: Vec<&str> = report.outcomes.iter().map(|o| o.name.as_str()).collect();
+
     assert_eq!(
-        get.text().await.expect("should have response text"),
-        "get_ok"
+        names,
+        vec!["request_two", "http_put", "http_default"],
+        "should be in the order requests are defined"
     );
 
-    let default = results
-        .remove("http_default")
-        .expect("should have the default (get) response");
+    let r2 = report.get("request_two").expect("should have outcome");
 
+    assert_eq!("POST", r2.request.method, "should snapshot method");
     assert_eq!(
-        default.status(),
-        reqwest::StatusCode::OK,
-        "default (get) should return ok",
+        "http://localhost:7878/test_endpoint_two#fragment_id", r2.request.url,
+        "should snapshot url"
+    );
+    assert_eq!(
+        Some("application/json"),
+        r2.request.header("content-type"),
+        "should snapshot headers"
+    );
+    assert!(
+        r2.request
+            .body
+            .as_ref()
+            .is_some_and(|body| body.starts_with(b"{")),
+        "should snapshot body"
+    );
+    assert_eq!(
+        Some(String::from("request_two")),
+        r2.detail.name,
+        "should include originating detail"
     );
+    assert_eq!(1, r2.attempts);
+    assert!(r2.error.is_none(), "should not have an error");
+    assert!(r2.is_success(), "should be successful");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_streams_outcomes() -> Result<(), Box<dyn Error>> {
+    let settings = Settings {
+        config_path: PathBuf::from("tests/e2e-config.yml"),
+        parallel: 2,
+        tags: vec![],
+        exclude_tags: vec![],
+        allow_duplicates: false,
+        har: None,
+        save_headers: false,
+        output_dir: None,
+        extract: None,
+        show_secrets: false,
+        verbose: 0,
+        reports: vec![],
+        command: None,
+        rate: None,
+        no_color: false,
+        output: OutputFormat::Ndjson,
+        request_names: vec![String::from("http_get"), String::from("http_put")],
+    };
+
+    start_server(7878).await;
+
+    let mut streamed = vec![];
+
+    let report = run::go_with(settings, |outcome| streamed.push(outcome.name.clone())).await?;
+
+    streamed.sort();
+
     assert_eq!(
-        default.text().await.expect("should have response text"),
-        "get_ok"
+        streamed,
+        vec!["http_get", "http_put"],
+        "should pass each outcome as it completes"
     );
+    assert_eq!(2, report.outcomes.len(), "should still report all outcomes");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_saves_responses() -> Result<(), Box<dyn Error>> {
+    let output_dir = std::env::temp_dir().join("corkscrew_test_run_saves_responses");
+
+    let _ = std::fs::remove_dir_all(&output_dir);
+
+    let settings = Settings {
+        config_path: PathBuf::from("tests/e2e-config.yml"),
+        parallel: 0,
+        tags: vec![],
+        exclude_tags: vec![],
+        allow_duplicates: false,
+        har: None,
+        save_headers: true,
+        output_dir: Some(output_dir.clone()),
+        extract: None,
+        show_secrets: false,
+        verbose: 0,
+        reports: vec![],
+        output: OutputFormat::Table,
+        no_color: false,
+        command: None,
+        rate: None,
+        request_names: vec![String::from("http_get")],
+    };
+
+    start_server(7878).await;
+
+    let report = run::go(settings).await?;
+
+    let response = report
+        .get("http_get")
+        .and_then(|outcome| outcome.response.as_ref())
+        .expect("should have response");
+
+    let saved = response.saved.as_ref().expect("should save response");
+
+    assert_eq!(saved.path, output_dir.join("http_get"));
+    assert_eq!(saved.size, 6, "should record size of saved body");
+    assert!(response.body.is_empty(), "should not hold body in memory");
+    assert_eq!(std::fs::read_to_string(&saved.path)?, "get_ok");
+
+    let headers = std::fs::read_to_string(output_dir.join("http_get.headers"))?;
+
+    assert!(
+        headers.starts_with("HTTP/1.1 200 OK\n"),
+        "should write status alongside"
+    );
+    assert!(
+        headers.contains("content-length: 6\n"),
+        "should write headers"
+    );
+
+    std::fs::remove_dir_all(&output_dir)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_http_config() -> Result<(), Box<dyn Error>> {
+    let settings = Settings {
+        config_path: PathBuf::from("tests/e2e-config.http"),
+        parallel: 0,
+        tags: vec![],
+        exclude_tags: vec![],
+        allow_duplicates: false,
+        har: None,
+        save_headers: false,
+        output_dir: None,
+        extract: None,
+        show_secrets: false,
+        verbose: 0,
+        reports: vec![],
+        output: OutputFormat::Table,
+        no_color: false,
+        command: None,
+        rate: None,
+        request_names: vec![],
+    };
+
+    start_server(7878).await;
+
+    let report = run::go(settings).await?;
+
+    for name in ["request_one", "request_two"] {
+        let status = report
+            .get(name)
+            .and_then(|outcome| outcome.response.as_ref())
+            .map(|response| response.status);
+
+        assert_eq!(status, Some(200), "should run {name} from .http file");
+    }
 
     Ok(())
 }
@@ -151,32 +372,57 @@ async fn test_panics_on_missing_config_file() {
         config_path: PathBuf::from("tests/missing.yml"),
         request_names: vec![],
         parallel: 0,
+        tags: vec![],
+        exclude_tags: vec![],
+        allow_duplicates: false,
+        har: None,
+        save_headers: false,
+        output_dir: None,
+        extract: None,
+        show_secrets: false,
+        verbose: 0,
+        reports: vec![],
+        output: OutputFormat::Table,
+        no_color: false,
+        command: None,
+        rate: None,
     };
 
     let _ = run::go(settings).await;
 }
 
+static SERVER: Once = Once::new();
+
 async fn start_server(port: u16) {
-    let listener =
-        TcpListener::bind(format!("localhost:{}", port)).expect("unable to bind to port");
-
-    let server = HttpServer::new(move || {
-        App::new()
-            .service(
-                web::resource("/test_http_methods")
-                    .route(web::delete().to(handler_methods))
-                    .route(web::put().to(handler_methods))
-                    .route(web::patch().to(handler_methods))
-                    .route(web::get().to(handler_methods)),
-            )
-            .service(web::resource("/test_endpoint_one").post(handler_one))
-            .service(web::resource("/test_endpoint_two").post(handler_two))
-    })
-    .listen(listener)
-    .expect("unable to start server")
-    .run();
-
-    tokio::spawn(server);
+    // The server outlives the runtime of the test that starts it, so start it
+    // once on its own thread and share it between tests.
+    SERVER.call_once(|| {
+        let listener =
+            TcpListener::bind(format!("localhost:{}", port)).expect("unable to bind to port");
+
+        thread::spawn(move || {
+            tokio::runtime::Runtime::new()
+                .expect("unable to start runtime")
+                .block_on(async move {
+                    HttpServer::new(move || {
+                        App::new()
+                            .service(
+                                web::resource("/test_http_methods")
+                                    .route(web::delete().to(handler_methods))
+                                    .route(web::put().to(handler_methods))
+                                    .route(web::patch().to(handler_methods))
+                                    .route(web::get().to(handler_methods)),
+                            )
+                            .service(web::resource("/test_endpoint_one").post(handler_one))
+                            .service(web::resource("/test_endpoint_two").post(handler_two))
+                    })
+                    .listen(listener)
+                    .expect("unable to start server")
+                    .run()
+                    .await
+                })
+        });
+    });
 }
 
 async fn handler_one(req: HttpRequest, params: web::Query<TestUrlParams>) -> HttpResponse {
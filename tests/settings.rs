@@ -1,14 +1,33 @@
 #[cfg(test)]
 mod test {
+    use clap::Parser;
     use corkscrew::types::cli::Cli;
-    use corkscrew::Settings;
-    use std::{error::Error, path::PathBuf};
+    use corkscrew::{
+        Bench, Command, Export, ExportFormat, Extract, Import, ImportFormat, OutputFormat, Rate,
+        Report, ReportFormat, Selection, Settings,
+    };
+    use std::{error::Error, path::PathBuf, time::Duration};
 
     #[test]
     fn test_cli_config_defaults() -> Result<(), Box<dyn Error>> {
         let config: Settings = Cli {
             config_path: None,
             parallel: None,
+            tags: vec![],
+            exclude_tags: vec![],
+            allow_duplicates: false,
+            har: None,
+            save_headers: false,
+            output_dir: None,
+            header_select: None,
+            select: None,
+            show_secrets: false,
+            verbose: 0,
+            reports: vec![],
+            no_color: false,
+            output: None,
+            command: None,
+            rate: None,
             request_names: vec![],
         }
         .try_into()?;
@@ -25,6 +44,21 @@ mod test {
         let config: Settings = Cli {
             config_path: Some(PathBuf::from("test.yml")),
             parallel: None,
+            tags: vec![],
+            exclude_tags: vec![],
+            allow_duplicates: false,
+            har: None,
+            save_headers: false,
+            output_dir: None,
+            header_select: None,
+            select: None,
+            show_secrets: false,
+            verbose: 0,
+            reports: vec![],
+            no_color: false,
+            output: None,
+            command: None,
+            rate: None,
             request_names: vec![],
         }
         .try_into()?;
@@ -41,6 +75,21 @@ mod test {
         let config: Settings = Cli {
             config_path: None,
             parallel: Some(4),
+            tags: vec![],
+            exclude_tags: vec![],
+            allow_duplicates: false,
+            har: None,
+            save_headers: false,
+            output_dir: None,
+            header_select: None,
+            select: None,
+            show_secrets: false,
+            verbose: 0,
+            reports: vec![],
+            no_color: false,
+            output: None,
+            command: None,
+            rate: None,
             request_names: vec![],
         }
         .try_into()?;
@@ -57,6 +106,21 @@ mod test {
         let config: Settings = Cli {
             config_path: None,
             parallel: None,
+            tags: vec![],
+            exclude_tags: vec![],
+            allow_duplicates: false,
+            har: None,
+            save_headers: false,
+            output_dir: None,
+            header_select: None,
+            select: None,
+            show_secrets: false,
+            verbose: 0,
+            reports: vec![],
+            no_color: false,
+            output: None,
+            command: None,
+            rate: None,
             request_names: vec![
                 String::from("test_one"),
                 String::from("test_two"),
@@ -84,6 +148,21 @@ mod test {
         let config: Settings = Cli {
             config_path: Some(PathBuf::from("test.yml")),
             parallel: Some(8),
+            tags: vec![],
+            exclude_tags: vec![],
+            allow_duplicates: false,
+            har: None,
+            save_headers: false,
+            output_dir: None,
+            header_select: None,
+            select: None,
+            show_secrets: false,
+            verbose: 0,
+            reports: vec![],
+            no_color: false,
+            output: None,
+            command: None,
+            rate: None,
             request_names: vec![
                 String::from("test_one"),
                 String::from("test_two"),
@@ -105,4 +184,352 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_cli_config_rate() -> Result<(), Box<dyn Error>> {
+        let config: Settings = Cli {
+            config_path: None,
+            parallel: Some(8),
+            tags: vec![],
+            exclude_tags: vec![],
+            allow_duplicates: false,
+            har: None,
+            save_headers: false,
+            output_dir: None,
+            header_select: None,
+            select: None,
+            show_secrets: false,
+            verbose: 0,
+            reports: vec![],
+            no_color: false,
+            output: None,
+            command: None,
+            rate: Some("5/s".parse()?),
+            request_names: vec![],
+        }
+        .try_into()?;
+
+        assert_eq!(config.parallel, 8);
+        assert_eq!(
+            config.rate,
+            Some(Rate {
+                requests: 5,
+                per: Duration::from_secs(1)
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_bench_command() -> Result<(), Box<dyn Error>> {
+        let config: Settings = Cli::try_parse_from([
+            "corkscrew",
+            "-f",
+            "test.yml",
+            "bench",
+            "test_one",
+            "--duration",
+            "30s",
+            "--concurrency",
+            "4",
+        ])?
+        .try_into()?;
+
+        assert_eq!(config.config_path, PathBuf::from("test.yml"));
+        assert_eq!(config.request_names, vec![] as Vec<String>);
+        assert_eq!(
+            config.command,
+            Some(Command::Bench(Bench {
+                request_names: vec![String::from("test_one")],
+                repeat: None,
+                duration: Some(Duration::from_secs(30)),
+                concurrency: 4,
+            }))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_request_names_without_command() -> Result<(), Box<dyn Error>> {
+        let config: Settings =
+            Cli::try_parse_from(["corkscrew", "test_one", "test_two"])?.try_into()?;
+
+        assert_eq!(config.command, None);
+        assert_eq!(
+            config.request_names,
+            vec![String::from("test_one"), String::from("test_two")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_reports() -> Result<(), Box<dyn Error>> {
+        let config: Settings = Cli::try_parse_from([
+            "corkscrew",
+            "--report",
+            "junit=reports/api.xml",
+            "--report",
+            "tap",
+        ])?
+        .try_into()?;
+
+        assert_eq!(
+            config.reports,
+            vec![
+                Report {
+                    format: ReportFormat::Junit,
+                    path: Some(PathBuf::from("reports/api.xml")),
+                },
+                Report {
+                    format: ReportFormat::Tap,
+                    path: None,
+                },
+            ]
+        );
+
+        assert!(
+            Cli::try_parse_from(["corkscrew", "--report", "xunit"]).is_err(),
+            "should reject unknown report formats"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_verbose() -> Result<(), Box<dyn Error>> {
+        let config: Settings =
+            Cli::try_parse_from(["corkscrew", "-vv", "--show-secrets"])?.try_into()?;
+
+        assert_eq!(config.verbose, 2);
+        assert!(config.show_secrets);
+
+        let config: Settings = Cli::try_parse_from(["corkscrew"])?.try_into()?;
+
+        assert_eq!(config.verbose, 0);
+        assert!(!config.show_secrets);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_select() -> Result<(), Box<dyn Error>> {
+        let config: Settings = Cli::try_parse_from([
+            "corkscrew",
+            "--select",
+            "$.data[*].id",
+            "--header-select",
+            "Location",
+        ])?
+        .try_into()?;
+
+        assert_eq!(
+            config.extract,
+            Some(Extract {
+                select: Some("$.data[*].id".parse()?),
+                header: Some(String::from("Location")),
+            })
+        );
+
+        let config: Settings = Cli::try_parse_from(["corkscrew"])?.try_into()?;
+
+        assert_eq!(config.extract, None, "should not extract by default");
+
+        assert!(
+            Cli::try_parse_from(["corkscrew", "--select", "data.id"]).is_err(),
+            "should reject invalid JSONPath"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_har() -> Result<(), Box<dyn Error>> {
+        let config: Settings =
+            Cli::try_parse_from(["corkscrew", "--har", "run.har"])?.try_into()?;
+
+        assert_eq!(config.har, Some(PathBuf::from("run.har")));
+
+        let config: Settings = Cli::try_parse_from(["corkscrew"])?.try_into()?;
+
+        assert_eq!(config.har, None, "should not write a HAR file by default");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_allow_duplicates() -> Result<(), Box<dyn Error>> {
+        let config: Settings =
+            Cli::try_parse_from(["corkscrew", "--allow-duplicates", "get_user"])?.try_into()?;
+
+        assert_eq!(
+            config.selection(&config.request_names),
+            Selection {
+                names: vec![String::from("get_user")],
+                tags: vec![],
+                exclude_tags: vec![],
+                allow_duplicates: true,
+            }
+        );
+
+        let config: Settings = Cli::try_parse_from(["corkscrew"])?.try_into()?;
+
+        assert!(
+            !config.allow_duplicates,
+            "should error on duplicates by default"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_tags() -> Result<(), Box<dyn Error>> {
+        let config: Settings = Cli::try_parse_from([
+            "corkscrew",
+            "--tag",
+            "smoke",
+            "--tag",
+            "auth",
+            "--exclude-tag",
+            "slow",
+        ])?
+        .try_into()?;
+
+        assert_eq!(
+            config.selection(&[]),
+            Selection {
+                names: vec![],
+                tags: vec![String::from("smoke"), String::from("auth")],
+                exclude_tags: vec![String::from("slow")],
+                allow_duplicates: false,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_export_command() -> Result<(), Box<dyn Error>> {
+        let config: Settings =
+            Cli::try_parse_from(["corkscrew", "export", "--format", "curl", "test_one"])?
+                .try_into()?;
+
+        assert_eq!(
+            config.command,
+            Some(Command::Export(Export {
+                request_names: vec![String::from("test_one")],
+                format: ExportFormat::Curl,
+            }))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_validate_command() -> Result<(), Box<dyn Error>> {
+        let config: Settings =
+            Cli::try_parse_from(["corkscrew", "validate", "-f", "api.yml", "-o", "json"])?
+                .try_into()?;
+
+        assert_eq!(config.command, Some(Command::Validate));
+        assert_eq!(config.config_path, PathBuf::from("api.yml"));
+        assert_eq!(config.output, OutputFormat::Json);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_import_command() -> Result<(), Box<dyn Error>> {
+        let config: Settings = Cli::try_parse_from([
+            "corkscrew",
+            "import",
+            "curl",
+            "commands.sh",
+            "--into",
+            "requests.yml",
+            "--group",
+            "api",
+        ])?
+        .try_into()?;
+
+        assert_eq!(
+            config.command,
+            Some(Command::Import(Import {
+                format: ImportFormat::Curl,
+                path: Some(PathBuf::from("commands.sh")),
+                into: Some(PathBuf::from("requests.yml")),
+                group: Some(String::from("api")),
+                hosts: vec![],
+                methods: vec![],
+                urls: vec![],
+                strip_cookies: false,
+                strip_auth: false,
+            }))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_import_openapi() -> Result<(), Box<dyn Error>> {
+        let config: Settings =
+            Cli::try_parse_from(["corkscrew", "import", "openapi", "spec.yaml"])?.try_into()?;
+
+        assert_eq!(
+            config.command,
+            Some(Command::Import(Import {
+                format: ImportFormat::OpenApi,
+                path: Some(PathBuf::from("spec.yaml")),
+                into: None,
+                group: None,
+                hosts: vec![],
+                methods: vec![],
+                urls: vec![],
+                strip_cookies: false,
+                strip_auth: false,
+            }))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_import_har() -> Result<(), Box<dyn Error>> {
+        let config: Settings = Cli::try_parse_from([
+            "corkscrew",
+            "import",
+            "har",
+            "session.har",
+            "--host",
+            "*.example.com",
+            "--method",
+            "GET",
+            "--method",
+            "POST",
+            "--url",
+            "*/api/*",
+            "--strip-cookies",
+            "--strip-auth",
+        ])?
+        .try_into()?;
+
+        assert_eq!(
+            config.command,
+            Some(Command::Import(Import {
+                format: ImportFormat::Har,
+                path: Some(PathBuf::from("session.har")),
+                into: None,
+                group: None,
+                hosts: vec![String::from("*.example.com")],
+                methods: vec![String::from("GET"), String::from("POST")],
+                urls: vec![String::from("*/api/*")],
+                strip_cookies: true,
+                strip_auth: true,
+            }))
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod test {
+    use corkscrew::{parser, validate, Problem};
+    use pretty_assertions::assert_eq;
+    use std::error::Error;
+
+    #[test]
+    fn test_validate_valid_requests() -> Result<(), Box<dyn Error>> {
+        let got = validate::validate(
+            r#"
+- name: api
+  host: localhost
+  variables:
+    id: "1"
+  requests:
+    - name: get_user
+      resource: /users/{{id}}
+    - name: create_user
+      resource: /users
+      method: post
+      body:
+        name: Ann
+"#,
+        );
+
+        assert_eq!(got, vec![], "should find no problems");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_problems() -> Result<(), Box<dyn Error>> {
+        let got = validate::validate(
+            r#"
+- name: api
+  host: localhost
+  variables:
+    version: "1"
+  requests:
+    - name: get_user
+      resource: /users/{{id}}
+      methd: get
+    - name: create_user
+      resource: /users
+      method: POST
+      body:
+        name: Ann
+      form:
+        name: Ann
+      headers:
+        x bad: "1"
+    - name: get_user
+      resource: /users
+    - resource: /unnamed
+- name: empty
+  host: localhost
+  requests:
+    - name: no_resource
+- resource: /
+  name: no_host
+- name: admin
+  host: localhost
+  requests:
+    - name: get_user
+      resource: /admin/users
+"#,
+        );
+
+        assert_eq!(
+            got,
+            vec![
+                Problem::error(
+                    "api.get_user",
+                    String::from("Unknown key 'methd', did you mean 'method'?")
+                ),
+                Problem::error(
+                    "api.get_user",
+                    String::from("Variable 'id' is not defined for 'get_user'.")
+                ),
+                Problem::error(
+                    "api.create_user",
+                    String::from("Methods are lowercase, use 'post' rather than 'POST'.")
+                ),
+                Problem::error(
+                    "api.create_user",
+                    String::from("Both body and form are set, but only one can be sent.")
+                ),
+                Problem::error(
+                    "api.create_user",
+                    String::from("Invalid header name 'x bad'.")
+                ),
+                Problem::error(
+                    "api[3]",
+                    String::from("Requests must have a name or requests.")
+                ),
+                Problem::warning(
+                    "empty.no_resource",
+                    String::from("No resource is set, so this request will never run.")
+                ),
+                Problem::warning(
+                    "empty",
+                    String::from("No requests in this group have a resource, so none will run.")
+                ),
+                Problem::error("no_host", String::from("Host is required.")),
+                Problem::error(
+                    "api.get_user",
+                    String::from(
+                        "More than one request is named 'api.get_user', so only the last would run. \
+                        Rename them, or use --allow-duplicates to run only the last."
+                    )
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_known_keys() -> Result<(), Box<dyn Error>> {
+        let error = parser::deserialize("- unknown: 1").unwrap_err();
+        let (_, expected) = error
+            .split_once("expected one of ")
+            .ok_or("no expected fields")?;
+
+        let keys: Vec<&str> = expected
+            .split(", ")
+            .map(|key| key.split('\'').nth(1).unwrap_or_default())
+            .collect();
+
+        assert!(keys.contains(&"save_headers"));
+
+        for key in keys {
+            let got = validate::validate(&format!("- {key}: ~\n"));
+
+            assert!(
+                !got.iter()
+                    .any(|problem| problem.message.starts_with("Unknown key")),
+                "should know the key '{key}'"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_invalid_yaml() -> Result<(), Box<dyn Error>> {
+        let got = validate::validate("- name: test\n  port: not_a_port\n");
+
+        assert_eq!(
+            got,
+            vec![Problem::error(
+                "",
+                String::from(
+                    "Invalid requests: 2:9: invalid type: string \"not_a_port\", expected u16"
+                )
+            )],
+            "should report where the file is invalid"
+        );
+
+        Ok(())
+    }
+}